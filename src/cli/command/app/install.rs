@@ -1,50 +1,159 @@
+use std::{collections::HashMap, process::ExitCode};
+
 use crate::{
-    core::shell::InstallRunner,
-    models::application::{Application, filter_apps},
-    models::system::SystemInfo,
-    print_info, print_warn,
+    cli::command::app::finish_report,
+    core::{
+        report::{InstallReport, ReportStatus},
+        shell::{InstallRunner, privilege::authenticate_for_run},
+    },
+    log_error, log_info, log_warn,
+    models::{
+        application::{Application, SelectedInstall, filter_apps},
+        package_manager::InstalledPackages,
+        repository::refresh_index,
+        system::SystemInfo,
+    },
+    print_error, print_info, print_warn,
 };
 
-pub fn install_apps_command(all: bool, server: bool, dry_run: bool) {
+pub fn install_apps_command(all: bool, server: bool, dry_run: bool) -> ExitCode {
     let apps = filter_apps(server, vec![]);
-    install_apps(apps, all, dry_run);
+    finish_report(&install_apps(apps, all, dry_run))
 }
 
-fn install_apps(apps: Vec<Application>, auto: bool, dry_run: bool) {
+fn install_apps(apps: Vec<Application>, auto: bool, dry_run: bool) -> InstallReport {
     let system = SystemInfo::new();
-    let current_os = system.os_type_raw();
+    let mut report = InstallReport::new("install", "local", dry_run);
 
     system.install_additional_pms();
 
-    for app in apps {
-        if app.is_installed() {
+    let selected: Vec<_> = apps.iter().map(|app| (app, app.select(&system))).collect();
+    let packages: Vec<_> = selected
+        .iter()
+        .filter_map(|(_, s)| s.as_ref()?.package())
+        .collect();
+    let installed = InstalledPackages::query(packages.iter().map(|(pm, p)| (*pm, p.as_str())));
+    let mut pending = Vec::new();
+
+    for (app, selection) in selected {
+        let Some(selection) = selection else {
+            if app.is_installed() {
+                print_info!("Skipping {}: already installed", app.name);
+                report.record(
+                    &app.name,
+                    ReportStatus::Skipped,
+                    None,
+                    Some("already installed".into()),
+                );
+                continue;
+            }
+            print_warn!("No valid install method found for {}", app.name);
+            log_info!("install", &app.name, "no install method for this OS");
+            report.record(
+                &app.name,
+                ReportStatus::NoMethod,
+                None,
+                Some(format!("no install method for {}", system.os_type_raw())),
+            );
+            continue;
+        };
+
+        if selection.is_installed(&installed) {
             print_info!("Skipping {}: already installed", app.name);
+            report.record(
+                &app.name,
+                ReportStatus::Skipped,
+                None,
+                Some("already installed".into()),
+            );
             continue;
         }
 
         if !auto && !app.prompt_install() {
             print_info!("Skipping installation of {}", app.name);
+            report.record(
+                &app.name,
+                ReportStatus::Skipped,
+                None,
+                Some("declined".into()),
+            );
             continue;
         }
 
-        let mut installed = false;
+        pending.push(selection);
+    }
 
-        for version in &app.versions {
-            for method in &version.install_methods {
-                if method.os.iter().any(|os| os.equals_ostype(&current_os)) {
-                    let runner = InstallRunner::new(&app, method, dry_run);
-                    runner.run_install();
-                    installed = true;
-                    break;
-                }
-            }
-            if installed {
-                break;
+    let _auth = if pending.is_empty() {
+        None
+    } else {
+        authenticate_for_run(dry_run)
+    };
+    let repo_errors = add_repositories(&pending, dry_run);
+
+    for selection in pending {
+        let SelectedInstall {
+            app,
+            version,
+            method,
+            context,
+        } = selection;
+
+        if let Some(e) = repo_errors.get(app.name.as_str()) {
+            report.record(&app.name, ReportStatus::Failed, None, Some(e.clone()));
+            continue;
+        }
+
+        let outcome = InstallRunner::new(app, version, method, context, dry_run).run_install();
+        let status = if outcome.succeeded() {
+            ReportStatus::Installed
+        } else {
+            ReportStatus::Failed
+        };
+        report.record(
+            &app.name,
+            status,
+            Some(outcome.duration),
+            outcome.error.or(outcome.warning),
+        );
+    }
+
+    report
+}
+
+/// Adds the vendor repositories of every pending install, then refreshes
+/// each package index once. Returns the error for each app whose
+/// repository could not be added.
+fn add_repositories<'a>(
+    pending: &[SelectedInstall<'a>],
+    dry_run: bool,
+) -> HashMap<&'a str, String> {
+    let mut errors = HashMap::new();
+    let mut refresh = Vec::new();
+
+    for selection in pending {
+        let method = selection.method;
+        let (Some(repo), Some(pm)) = (&method.repository, method.package_manager) else {
+            continue;
+        };
+
+        match repo.add(pm, &selection.context, dry_run) {
+            Ok(()) if !refresh.contains(&pm) => refresh.push(pm),
+            Ok(()) => {}
+            Err(e) => {
+                let msg = format!("failed to add repository {}: {e}", repo.name);
+                print_error!("❌ {}", msg);
+                log_error!("install", &selection.app.name, &msg);
+                errors.insert(selection.app.name.as_str(), msg);
             }
         }
+    }
 
-        if !installed {
-            print_warn!("No valid install method found for {}", app.name);
+    for pm in refresh {
+        if let Err(e) = refresh_index(pm, dry_run) {
+            print_warn!("Failed to refresh {} package index: {}", pm.name(), e);
+            log_warn!("install", pm.name(), &format!("index refresh failed: {e}"));
         }
     }
+
+    errors
 }
@@ -1,13 +1,21 @@
-use crate::models::{
-    application::list_supported_applications,
-    category::{Category, list_categories},
+use crate::{
+    config::TranquilityConfig,
+    core::report::InstallReport,
+    log_error,
+    models::{
+        application::list_supported_applications,
+        category::{Category, list_categories},
+    },
+    print_info,
 };
 use clap::{Args, Subcommand};
+use std::process::ExitCode;
 
 use crate::cli::print_subcommand_help;
 
 pub mod install;
 pub mod uninstall;
+pub mod upgrade;
 
 #[derive(Args, Debug)]
 pub struct AppCommand {
@@ -31,6 +39,17 @@ pub enum AppSubcommand {
         all: bool,
         #[arg(long)]
         server: bool,
+        /// Keep the vendor repositories the apps were installed from
+        #[arg(long)]
+        keep_repo: bool,
+    },
+
+    /// Upgrade installed applications through their package manager
+    Upgrade {
+        #[arg(long)]
+        all: bool,
+        #[arg(long)]
+        server: bool,
     },
 
     /// List all categories
@@ -45,13 +64,20 @@ pub enum AppSubcommand {
     },
 }
 
-pub fn handle_app_command(cmd: AppCommand, dry_run: bool) {
+pub fn handle_app_command(cmd: AppCommand, dry_run: bool) -> ExitCode {
     match cmd.command {
         Some(AppSubcommand::Install { all, server }) => {
-            install::install_apps_command(all, server, dry_run);
+            return install::install_apps_command(all, server, dry_run);
+        }
+        Some(AppSubcommand::Uninstall {
+            all,
+            server,
+            keep_repo,
+        }) => {
+            return uninstall::uninstall_apps_command(all, server, keep_repo, dry_run);
         }
-        Some(AppSubcommand::Uninstall { all, server }) => {
-            uninstall::uninstall_apps_command(all, server, dry_run);
+        Some(AppSubcommand::Upgrade { all, server }) => {
+            return upgrade::upgrade_apps_command(all, server, dry_run);
         }
         Some(AppSubcommand::Categories {}) => list_categories(),
         Some(AppSubcommand::List { server, category }) => {
@@ -59,4 +85,25 @@ pub fn handle_app_command(cmd: AppCommand, dry_run: bool) {
         }
         None => print_subcommand_help("apps"),
     }
+    ExitCode::SUCCESS
+}
+
+/// Prints and saves `report`, failing when any app failed.
+pub fn finish_report(report: &InstallReport) -> ExitCode {
+    report.print_summary();
+    let cfg = TranquilityConfig::load_once();
+    match report.write_json(&cfg.log_directory) {
+        Ok(path) => print_info!("📄 Report written to {}", path.display()),
+        Err(e) => log_error!(
+            "report",
+            &report.action,
+            &format!("❌ Failed to write report: {e}")
+        ),
+    }
+
+    if report.has_failures() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
 }
@@ -1,50 +1,124 @@
+use std::process::ExitCode;
+
 use crate::{
-    core::shell::InstallRunner,
+    cli::command::app::finish_report,
+    core::{
+        report::{InstallReport, ReportStatus},
+        shell::{InstallRunner, privilege::authenticate_for_run},
+    },
     models::{
-        application::{Application, filter_apps},
+        application::{Application, SelectedInstall, filter_apps},
+        package_manager::InstalledPackages,
         system::SystemInfo,
     },
     print_info, print_warn,
 };
 
-pub fn uninstall_apps_command(all: bool, server: bool, dry_run: bool) {
+pub fn uninstall_apps_command(all: bool, server: bool, keep_repo: bool, dry_run: bool) -> ExitCode {
     let apps = filter_apps(server, vec![]);
-    uninstall_apps(apps, all, dry_run);
+    finish_report(&uninstall_apps(apps, all, keep_repo, dry_run))
 }
 
-fn uninstall_apps(apps: Vec<Application>, auto: bool, dry_run: bool) {
+fn uninstall_apps(
+    apps: Vec<Application>,
+    auto: bool,
+    keep_repo: bool,
+    dry_run: bool,
+) -> InstallReport {
     let system = SystemInfo::new();
-    let current_os = system.os_type_raw();
+    let mut report = InstallReport::new("uninstall", "local", dry_run);
+
+    let selected: Vec<_> = apps.iter().map(|app| (app, app.select(&system))).collect();
+    let packages: Vec<_> = selected
+        .iter()
+        .filter_map(|(_, s)| s.as_ref()?.package())
+        .collect();
+    let installed = InstalledPackages::query(packages.iter().map(|(pm, p)| (*pm, p.as_str())));
+    let is_installed = |app: &Application, selection: &Option<SelectedInstall>| match selection {
+        Some(selection) => selection.is_installed(&installed),
+        None => app.is_installed(),
+    };
+
+    // Apps uninstalled so far no longer hold on to their repository
+    let mut removed: Vec<&str> = Vec::new();
+    let repo_in_use = |app: &Application, repo: &str, removed: &[&str]| {
+        selected.iter().any(|(other, selection)| {
+            other.name != app.name
+                && !removed.contains(&other.name.as_str())
+                && selection.as_ref().is_some_and(|s| {
+                    s.method.repository.as_ref().is_some_and(|r| r.name == repo)
+                        && s.is_installed(&installed)
+                })
+        })
+    };
+
+    let _auth = if selected.iter().any(|(app, s)| is_installed(app, s)) {
+        authenticate_for_run(dry_run)
+    } else {
+        None
+    };
 
-    for app in apps {
-        if !app.is_installed() {
+    for (app, selection) in &selected {
+        if !is_installed(app, selection) {
             print_info!("Skipping {}: not installed", app.name);
+            report.record(
+                &app.name,
+                ReportStatus::Skipped,
+                None,
+                Some("not installed".into()),
+            );
             continue;
         }
 
         if !auto && !app.prompt_uninstall() {
             print_info!("Skipping uninstall of {}", app.name);
+            report.record(
+                &app.name,
+                ReportStatus::Skipped,
+                None,
+                Some("declined".into()),
+            );
             continue;
         }
 
-        let mut uninstalled = false;
-
-        for version in &app.versions {
-            for method in &version.install_methods {
-                if method.os.iter().any(|os| os.equals_ostype(&current_os)) {
-                    let runner = InstallRunner::new(&app, method, dry_run);
-                    runner.run_uninstall();
-                    uninstalled = true;
-                    break;
+        match selection {
+            Some(SelectedInstall {
+                version,
+                method,
+                context,
+                ..
+            }) => {
+                let shared = method
+                    .repository
+                    .as_ref()
+                    .filter(|repo| repo_in_use(app, &repo.name, &removed));
+                if let (Some(repo), false) = (shared, keep_repo) {
+                    print_info!(
+                        "Keeping repository {}: other installed apps still use it",
+                        repo.name
+                    );
                 }
+                let outcome = InstallRunner::new(app, version, method, context.clone(), dry_run)
+                    .run_uninstall(keep_repo || shared.is_some());
+                let status = if outcome.succeeded() {
+                    removed.push(&app.name);
+                    ReportStatus::Uninstalled
+                } else {
+                    ReportStatus::Failed
+                };
+                report.record(&app.name, status, Some(outcome.duration), outcome.error);
             }
-            if uninstalled {
-                break;
+            None => {
+                print_warn!("No valid uninstall method found for {}", app.name);
+                report.record(
+                    &app.name,
+                    ReportStatus::NoMethod,
+                    None,
+                    Some(format!("no uninstall method for {}", system.os_type_raw())),
+                );
             }
         }
-
-        if !uninstalled {
-            print_warn!("No valid uninstall method found for {}", app.name);
-        }
     }
+
+    report
 }
@@ -0,0 +1,83 @@
+use std::process::ExitCode;
+
+use crate::{
+    cli::command::app::finish_report,
+    core::{
+        report::{InstallReport, ReportStatus},
+        shell::{InstallRunner, privilege::authenticate_for_run},
+    },
+    models::{
+        application::{Application, SelectedInstall, filter_apps},
+        package_manager::InstalledPackages,
+        system::SystemInfo,
+    },
+    print_info,
+};
+
+pub fn upgrade_apps_command(all: bool, server: bool, dry_run: bool) -> ExitCode {
+    let apps = filter_apps(server, vec![]);
+    finish_report(&upgrade_apps(apps, all, dry_run))
+}
+
+/// Upgrades every installed app whose method names a package; apps
+/// installed through steps are skipped.
+fn upgrade_apps(apps: Vec<Application>, auto: bool, dry_run: bool) -> InstallReport {
+    let system = SystemInfo::new();
+    let mut report = InstallReport::new("upgrade", "local", dry_run);
+
+    let selected: Vec<SelectedInstall> =
+        apps.iter().filter_map(|app| app.select(&system)).collect();
+    let packages: Vec<_> = selected.iter().filter_map(|s| s.package()).collect();
+    let installed = InstalledPackages::query(packages.iter().map(|(pm, p)| (*pm, p.as_str())));
+    let upgradable: Vec<SelectedInstall> = selected
+        .into_iter()
+        .filter(|s| s.method.steps.is_none() && s.package().is_some())
+        .filter(|s| {
+            let found = s.is_installed(&installed);
+            if !found {
+                print_info!("Skipping {}: not installed", s.app.name);
+                report.record(
+                    &s.app.name,
+                    ReportStatus::Skipped,
+                    None,
+                    Some("not installed".into()),
+                );
+            }
+            found
+        })
+        .collect();
+
+    let _auth = if upgradable.is_empty() {
+        None
+    } else {
+        authenticate_for_run(dry_run)
+    };
+
+    for SelectedInstall {
+        app,
+        version,
+        method,
+        context,
+    } in upgradable
+    {
+        if !auto && !app.prompt_upgrade() {
+            print_info!("Skipping upgrade of {}", app.name);
+            report.record(
+                &app.name,
+                ReportStatus::Skipped,
+                None,
+                Some("declined".into()),
+            );
+            continue;
+        }
+        let outcome = InstallRunner::new(app, version, method, context, dry_run).run_upgrade();
+        let status = if outcome.succeeded() {
+            ReportStatus::Upgraded
+        } else {
+            ReportStatus::Failed
+        };
+        report.record(&app.name, status, Some(outcome.duration), outcome.error);
+    }
+
+    report
+}
@@ -1,14 +1,18 @@
 // Module: Command/Doctor
 // Location: cli/src/command/doctor/mod.rs
 use crate::{
-    cli::command::{config, vps},
+    cli::command::{config, vps, vps::status::check_all},
     config::TranquilityConfig,
     log_error,
-    models::{application::get_apps, package_manager::PackageManager, system::SystemInfo},
-    print_success, print_warn,
+    models::{
+        application::get_apps,
+        system::SystemInfo,
+        vps::json::{VpsConfig, VpsEntry},
+    },
+    print_info, print_success, print_warn,
 };
-use clap::{Args, arg};
-use std::{fs, path::Path};
+use clap::Args;
+use std::{fs, path::Path, time::Duration};
 
 #[derive(Args, Debug)]
 pub struct DoctorCommand {
@@ -44,7 +48,7 @@ pub fn doctor_command(cmd: DoctorCommand, dry_run: bool) {
 
     // Package manager check
     println!("🔍 Checking common package managers:");
-    for pm in PackageManager::supported_on_os(sys.os_type_raw()) {
+    for pm in sys.supported_package_managers() {
         if pm.check_installed() {
             print_success!("✅ {} is installed", pm.name());
         } else {
@@ -52,6 +56,8 @@ pub fn doctor_command(cmd: DoctorCommand, dry_run: bool) {
         }
     }
 
+    check_vps_hosts(&cfg.vps_file);
+
     // App schema check
     let app_count = get_apps().applications.len();
     if app_count == 0 {
@@ -63,6 +69,38 @@ pub fn doctor_command(cmd: DoctorCommand, dry_run: bool) {
     println!("\n🩺 Doctor check complete.\n");
 }
 
+/// Probes every VPS entry, without logging in.
+fn check_vps_hosts(path: &Path) {
+    let Ok(vps_config) = VpsConfig::load_from_file(&path.to_path_buf()) else {
+        return;
+    };
+    if vps_config.vps.is_empty() {
+        return;
+    }
+
+    println!("🔍 Checking VPS hosts:");
+    let targets: Vec<&VpsEntry> = vps_config.vps.iter().collect();
+    for status in check_all(&targets, &vps_config, Duration::from_secs(3), false) {
+        match (status.reachable, status.latency_ms) {
+            (Some(true), Some(ms)) => print_success!(
+                "✅ {} is reachable at {}:{} ({:.0}ms)",
+                status.id,
+                status.host,
+                status.port,
+                ms
+            ),
+            (Some(false), _) => print_warn!(
+                "⚠️  {} is unreachable at {}:{}: {}",
+                status.id,
+                status.host,
+                status.port,
+                status.error.unwrap_or_default()
+            ),
+            _ => print_info!("ℹ️  {} is behind a jump host; skipped", status.id),
+        }
+    }
+}
+
 fn check_file(path: &Path, label: &str) {
     if path.exists() {
         print_success!("✅ {} exists: {}", label, path.display());
@@ -2,7 +2,6 @@
 // Location: cli/src/command/font/install.rs
 
 use colored::Colorize;
-use reqwest::blocking::get;
 use std::{
     fs::{self, File},
     io::Write,
@@ -14,7 +13,7 @@ use super::refresh::auto_refresh;
 use crate::{
     core::{
         font::{get_font_dir, is_font_installed},
-        zip,
+        retry, zip,
     },
     log_error, log_info, log_warn,
     models::font::NERD_FONT_LIST,
@@ -63,10 +62,9 @@ pub fn install_nerd_font(font: &str) {
 
     print_info!("Downloading {}", font);
     log_info!("install", font, "downloading");
-    match get(&font_url) {
-        Ok(response) => {
+    match retry::download(&font_url) {
+        Ok(content) => {
             let mut file = File::create(&zip_path).expect("Failed to create ZIP file");
-            let content = response.bytes().expect("Failed to read ZIP content");
             file.write_all(&content).expect("Failed to write ZIP file");
         }
         Err(e) => {
@@ -29,6 +29,9 @@ pub struct VpsAddCommand {
     port: Option<String>,
     #[arg(long = "private-key")]
     private_key: Option<String>,
+    /// Tag to select the entry by in fleet commands; repeatable
+    #[arg(long)]
+    tag: Vec<String>,
 }
 
 pub fn vps_command_add(cmd: VpsAddCommand, dry_run: bool) {
@@ -216,6 +219,8 @@ fn prompt_and_add_vps(cmd: VpsAddCommand, dry_run: bool) -> io::Result<()> {
         port,
         private_key,
         post_connect_script,
+        tags: cmd.tag,
+        ..VpsEntry::default()
     };
 
     print_info!("\n📦 New VPS entry:");
@@ -1,15 +1,23 @@
 use clap::Args;
-use dialoguer::{Select, theme::ColorfulTheme};
-use std::io;
+use dialoguer::{Confirm, theme::ColorfulTheme};
+use std::{io, time::Duration};
 
 use crate::{
+    cli::command::vps::select_vps,
     config::TranquilityConfig,
     core::shell::ShellCommand,
     log_error,
-    models::vps::json::{VpsConfig, VpsEntry},
+    models::vps::{
+        json::{VpsConfig, VpsEntry},
+        ssh, status,
+        tunnel::TunnelSpec,
+    },
     print_info, print_warn,
 };
 
+/// How long to wait for the host to answer before warning.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
 #[derive(Args, Debug)]
 pub struct VpsConnectCommand {
     #[arg(long)]
@@ -29,59 +37,20 @@ fn connect_to_vps(id: Option<String>, copy_id: Option<String>, dry_run: bool) ->
     let mut vps_config = VpsConfig::load_from_file(&config.vps_file)?;
     vps_config.fix();
 
-    if vps_config.vps.is_empty() {
-        print_warn!("⚠️ No VPS entries found in your configuration.");
+    let Some(vps) = select_vps(&vps_config, id.as_deref(), "🔌 Select a VPS to connect to")?
+    else {
         return Ok(());
-    }
-
-    let vps = match id {
-        Some(ref id) => vps_config.vps.iter().find(|v| v.id.as_deref() == Some(id)),
-        None => {
-            let options: Vec<String> = vps_config
-                .vps
-                .iter()
-                .map(|v| {
-                    let user = v.effective_user();
-                    let name = v.name.clone().unwrap_or_else(|| "-".into());
-                    format!("{}@{} ({})", user, v.host, name)
-                })
-                .collect();
-
-            let selection = Select::with_theme(&ColorfulTheme::default())
-                .with_prompt("🔌 Select a VPS to connect to")
-                .items(&options)
-                .default(0)
-                .interact()
-                .map_err(|e| io::Error::other(format!("Prompt failed: {e}")))?;
-
-            Some(&vps_config.vps[selection])
-        }
-    };
-
-    let vps = match vps {
-        Some(v) => v,
-        None => {
-            print_warn!("❌ No VPS found with the specified ID.");
-            return Ok(());
-        }
     };
 
     let port = vps.effective_port().to_string();
-    let user = vps.effective_user();
-    let host = &vps.host;
-    let remote = format!("{user}@{host}");
+    let remote = ssh::destination(vps);
+    let connection = ssh::ssh_args(vps, &vps_config.vps)?;
 
     /* ---------- build the ssh args ---------- */
-    let mut ssh_args = vec!["-tt"];
-    if let Some(ref key) = vps.private_key {
-        ssh_args.push("-i");
-        ssh_args.push(key);
-    }
-    if port != "22" {
-        ssh_args.push("-p");
-        ssh_args.push(&port);
-    }
-    ssh_args.push(&remote);
+    let mut ssh_args = vec!["-tt".to_string()];
+    ssh_args.extend(connection.iter().cloned());
+    ssh_args.extend(TunnelSpec::from_entry(vps).forward_args());
+    ssh_args.push(remote.clone());
 
     /* ---------- optional ssh-copy-id ---------- */
     if copy_id.is_some() {
@@ -92,6 +61,16 @@ fn connect_to_vps(id: Option<String>, copy_id: Option<String>, dry_run: bool) ->
             copy_args.push("-p");
             copy_args.push(&port);
         }
+        // ssh-copy-id's -i names the key to install, so only -o options carry over
+        let mut connection = connection.iter();
+        while let Some(arg) = connection.next() {
+            if arg == "-o"
+                && let Some(option) = connection.next()
+            {
+                copy_args.push("-o");
+                copy_args.push(option);
+            }
+        }
         if let Some(ref key_path) = pub_key_opt {
             copy_args.push("-i");
             copy_args.push(key_path);
@@ -113,6 +92,22 @@ fn connect_to_vps(id: Option<String>, copy_id: Option<String>, dry_run: bool) ->
         return Ok(());
     }
 
+    // Jump hosts can reach what this machine can't, so only direct
+    // connections are probed
+    if vps.proxy_jump.is_none()
+        && let Err(e) = status::probe_tcp(&vps.host, vps.effective_port(), PROBE_TIMEOUT)
+    {
+        print_warn!("⚠️ {}:{} is not reachable: {e}", vps.host, port);
+        let proceed = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Connect anyway?")
+            .default(false)
+            .interact()
+            .map_err(|e| io::Error::other(format!("Prompt failed: {e}")))?;
+        if !proceed {
+            return Ok(());
+        }
+    }
+
     print_info!("🔌 Connecting to {remote} on port {port}...");
     ShellCommand::new("ssh")
         .with_args(ssh_args)
@@ -1,112 +1,132 @@
-// Module: Command/Vps/Connect
-// Location: cli/src/command/vps/connect.rs
+// Module: Command/Vps/Copy
+// Location: cli/src/command/vps/copy.rs
 
 use clap::Args;
-use dialoguer::{theme::ColorfulTheme, Select};
-use std::io;
-use tranquility::{log_error, print_info, print_warn};
+use std::{io, process::ExitCode};
 
 use crate::{
-    model::config::TranquilityConfig,
-    model::vps::json::{VpsConfig},
-    shell::ShellCommand,
+    cli::command::vps::select_vps,
+    config::TranquilityConfig,
+    core::shell::command::command_exists,
+    log_error, log_info,
+    models::vps::{
+        json::VpsConfig,
+        transfer::{Endpoint, TransferOptions, TransferTool, transfer_command},
+    },
+    print_info,
 };
 
 #[derive(Args, Debug)]
 pub struct VpsCopyCommand {
+    /// VPS to copy to, or from with --local or --remote
     #[arg(long)]
     id: Option<String>,
+    /// Path the files are copied to
     #[arg(long)]
-    destination: Option<String>,
+    destination: String,
+    /// Path of the files to copy
     #[arg(long)]
-    source: Option<String>,
-    /// Copy files from remote server to remote server
-    #[arg(long)]
-    remote : bool,
+    source: String,
+    /// Copy files from the --id server to the --to server
+    #[arg(long, requires = "to", conflicts_with = "local")]
+    remote: bool,
     /// Copy files from the remote server to the local machine
     #[arg(long)]
-    local : bool,
-    
-    
-    
+    local: bool,
+    /// Destination VPS for --remote
+    #[arg(long)]
+    to: Option<String>,
+    /// Copy directories recursively
+    #[arg(short, long)]
+    recursive: bool,
+    /// Show transfer progress
+    #[arg(long)]
+    progress: bool,
+    /// Use scp even when rsync is installed
+    #[arg(long)]
+    scp: bool,
 }
 
-pub fn vps_command_copy(cmd: VpsCopyCommand, dry_run: bool) {
-    if let Err(e) = connect_to_vps(cmd.id, dry_run) {
-        log_error!("copy", "vps", &format!("❌ Failed to copy: {e}"));
+pub fn vps_command_copy(cmd: VpsCopyCommand, dry_run: bool) -> ExitCode {
+    match copy_files(cmd, dry_run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            log_error!("copy", "vps", &format!("❌ Failed to copy: {e}"));
+            ExitCode::FAILURE
+        }
     }
 }
 
-fn connect_to_vps(id: Option<String>, dry_run: bool) -> io::Result<()> {
-    let config = TranquilityConfig::load_or_init()?;
+fn copy_files(cmd: VpsCopyCommand, dry_run: bool) -> io::Result<()> {
+    let config = TranquilityConfig::load_once();
     let mut vps_config = VpsConfig::load_from_file(&config.vps_file)?;
     vps_config.fix();
 
-    if vps_config.vps.is_empty() {
-        print_warn!("⚠️ No VPS entries found in your configuration.");
+    let Some(vps) = select_vps(
+        &vps_config,
+        cmd.id.as_deref(),
+        "📦 Select a VPS to copy with",
+    )?
+    else {
         return Ok(());
-    }
-
-    let vps = match id {
-        Some(ref id) => vps_config.vps.iter().find(|v| v.id.as_deref() == Some(id)),
-        None => {
-            let options: Vec<String> = vps_config
-                .vps
-                .iter()
-                .map(|v| {
-                    let user = v.effective_user();
-                    let name = v.name.clone().unwrap_or_else(|| "-".into());
-                    format!("{}@{} ({})", user, v.host, name)
-                })
-                .collect();
-
-            let selection = Select::with_theme(&ColorfulTheme::default())
-                .with_prompt("🔌 Select a VPS to connect to")
-                .items(&options)
-                .default(0)
-                .interact()
-                .map_err(|e| io::Error::other( format!("Prompt failed: {e}")))?;
-
-            Some(&vps_config.vps[selection])
-        }
     };
 
-    let vps = match vps {
-        Some(v) => v,
-        None => {
-            print_warn!("❌ No VPS found with the specified ID.");
-            return Ok(());
-        }
+    let remote = Endpoint::Remote {
+        vps,
+        path: &cmd.source,
+    };
+    let (from, to) = if cmd.remote {
+        let target_id = cmd.to.as_deref().unwrap_or_default();
+        let target = vps_config
+            .vps
+            .iter()
+            .find(|v| v.id.as_deref() == Some(target_id))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no VPS found with the ID {target_id}"),
+                )
+            })?;
+        (
+            remote,
+            Endpoint::Remote {
+                vps: target,
+                path: &cmd.destination,
+            },
+        )
+    } else if cmd.local {
+        (remote, Endpoint::Local(&cmd.destination))
+    } else {
+        (
+            Endpoint::Local(&cmd.source),
+            Endpoint::Remote {
+                vps,
+                path: &cmd.destination,
+            },
+        )
     };
 
-    let port = vps.effective_port().to_string();
-    let user = vps.effective_user();
-    let host = &vps.host;
-
-    let mut args = vec!["-tt"];
-
-    if let Some(ref key) = vps.private_key {
-        args.push("-i");
-        args.push(key);
-    }
-
-    if port != "22" {
-        args.push("-p");
-        args.push(&port);
-    }
+    // rsync can't copy between two remotes, and needs to exist locally
+    let tool = if cmd.scp || cmd.remote || !command_exists("rsync") {
+        TransferTool::Scp
+    } else {
+        TransferTool::Rsync
+    };
+    let options = TransferOptions {
+        recursive: cmd.recursive,
+        progress: cmd.progress,
+    };
 
-    let remote = format!("{user}@{host}");
-    args.push(&remote);
+    print_info!(
+        "📦 Copying {} to {} with {}",
+        cmd.source,
+        cmd.destination,
+        tool
+    );
+    transfer_command(tool, &from, &to, options, &vps_config.vps)?.run(dry_run)?;
 
-    if dry_run {
-        print_warn!("(dry run) Would run: ssh {}", args.join(" "));
-        return Ok(());
+    if !dry_run {
+        log_info!("copy", vps.effective_id(), "success");
     }
-
-    print_info!("🔌 Connecting to {remote} on port {port}...");
-    ShellCommand::new("ssh")
-        .with_args(args)
-        .run_interactive(false)?;
-
     Ok(())
 }
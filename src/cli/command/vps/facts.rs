@@ -0,0 +1,133 @@
+// Module: Command/Vps/Facts
+// Location: cli/src/command/vps/facts.rs
+
+use clap::Args;
+use std::{io, path::PathBuf, process::ExitCode};
+
+use crate::{
+    cli::command::vps::select_vps,
+    config::TranquilityConfig,
+    core::shell::ShellCommand,
+    log_error, log_info,
+    models::vps::{
+        facts::{self, FACTS_SCRIPT, RemoteFacts},
+        fleet::{self, Selector},
+        json::{VpsConfig, VpsEntry},
+        ssh,
+    },
+    print_info, print_warn,
+};
+
+#[derive(Args, Debug)]
+pub struct VpsFactsCommand {
+    /// VPS to gather facts from; repeatable
+    #[arg(long)]
+    id: Vec<String>,
+    /// Gather from every VPS with this tag; repeatable
+    #[arg(long)]
+    tag: Vec<String>,
+    /// Gather from every VPS
+    #[arg(long, conflicts_with_all = ["id", "tag"])]
+    all: bool,
+    /// Show the facts gathered last time instead of connecting
+    #[arg(long)]
+    cached: bool,
+    /// Print the facts as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+pub fn vps_command_facts(cmd: VpsFactsCommand, dry_run: bool) -> ExitCode {
+    vps_facts(cmd, dry_run).unwrap_or_else(|e| {
+        log_error!("facts", "vps", &format!("❌ Failed to gather facts: {e}"));
+        ExitCode::FAILURE
+    })
+}
+
+/// Where gathered facts are cached, one JSON file per VPS.
+pub fn facts_dir() -> io::Result<PathBuf> {
+    Ok(TranquilityConfig::config_dir()?.join("facts"))
+}
+
+/// Fails when facts are missing for any target, gathered or cached.
+fn vps_facts(cmd: VpsFactsCommand, dry_run: bool) -> io::Result<ExitCode> {
+    let config = TranquilityConfig::load_once();
+    let mut vps_config = VpsConfig::load_from_file(&config.vps_file)?;
+    vps_config.fix();
+
+    let selector = Selector {
+        ids: cmd.id.clone(),
+        tags: cmd.tag.clone(),
+        all: cmd.all,
+    };
+    let targets: Vec<&VpsEntry> = if selector.is_fleet() || !cmd.id.is_empty() {
+        selector.select(&vps_config.vps)?
+    } else {
+        match select_vps(&vps_config, None, "🔎 Select a VPS to gather facts from")? {
+            Some(vps) => vec![vps],
+            None => return Ok(ExitCode::SUCCESS),
+        }
+    };
+
+    let dir = facts_dir()?;
+    let mut gathered = Vec::new();
+    let mut failed = 0;
+
+    if cmd.cached {
+        for vps in targets {
+            match RemoteFacts::load(&dir, vps.effective_id()) {
+                Some(facts) => gathered.push(facts),
+                None => {
+                    print_warn!(
+                        "⚠️ No facts cached for {}; run without --cached first.",
+                        vps.effective_id()
+                    );
+                    failed += 1;
+                }
+            }
+        }
+    } else if dry_run {
+        for vps in targets {
+            let args = ssh::ssh_args(vps, &vps_config.vps)?;
+            ShellCommand::from_remote_script(&ssh::destination(vps), &args, FACTS_SCRIPT, false)
+                .dry_run();
+        }
+        return Ok(ExitCode::SUCCESS);
+    } else {
+        print_info!("🔎 Gathering facts from {} VPS...", targets.len());
+        let results = fleet::run_parallel(&targets, 8, |vps| facts::gather(vps, &vps_config.vps));
+        for (vps, result) in targets.iter().zip(results) {
+            match result {
+                Ok(facts) => {
+                    facts.save(&dir)?;
+                    log_info!("facts", vps.effective_id(), "facts gathered");
+                    gathered.push(facts);
+                }
+                Err(e) => {
+                    log_error!(
+                        "facts",
+                        vps.effective_id(),
+                        &format!("❌ Failed to gather facts: {e}")
+                    );
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    if cmd.json {
+        let json = serde_json::to_string_pretty(&gathered)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        println!("{json}");
+    } else {
+        for facts in &gathered {
+            println!("{}", facts.to_pretty_string());
+        }
+    }
+
+    if failed > 0 {
+        Ok(ExitCode::FAILURE)
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
+}
@@ -19,6 +19,8 @@ pub struct VpsListCommand {
     #[arg(long)]
     host: Option<String>,
     #[arg(long)]
+    tag: Option<String>,
+    #[arg(long)]
     dry_run: bool,
 }
 
@@ -34,15 +36,22 @@ struct VPSDisplay {
     host: String,
     #[tabled(rename = "Port")]
     port: String,
+    #[tabled(rename = "Tags")]
+    tags: String,
 }
 
 pub fn vps_command_list(cmd: VpsListCommand, dry_run: bool) {
-    if let Err(e) = vps_list(cmd.user, cmd.host, dry_run) {
+    if let Err(e) = vps_list(cmd.user, cmd.host, cmd.tag, dry_run) {
         print_warn!("❌ Failed to list VPS entries: {e}");
     }
 }
 
-pub fn vps_list(user: Option<String>, host: Option<String>, dry_run: bool) -> io::Result<()> {
+pub fn vps_list(
+    user: Option<String>,
+    host: Option<String>,
+    tag: Option<String>,
+    dry_run: bool,
+) -> io::Result<()> {
     let mut vps_entries: Vec<VpsEntry>;
 
     if dry_run {
@@ -56,14 +65,17 @@ pub fn vps_list(user: Option<String>, host: Option<String>, dry_run: bool) -> io
             print_info!("Would filter by host: {h}");
         }
 
+        if let Some(ref t) = tag {
+            print_info!("Would filter by tag: {t}");
+        }
+
         vps_entries = vec![VpsEntry {
             id: Some("dry-run-id".into()),
             name: Some("DryRunVPS".into()),
             user: Some("dryuser".into()),
             host: "dry.run.host".into(),
             port: Some("2222".into()),
-            private_key: None,
-            post_connect_script: None,
+            ..VpsEntry::default()
         }];
     } else {
         let config = TranquilityConfig::load_once();
@@ -80,6 +92,10 @@ pub fn vps_list(user: Option<String>, host: Option<String>, dry_run: bool) -> io
         vps_entries.retain(|v| v.host == host_filter);
     }
 
+    if let Some(tag_filter) = tag {
+        vps_entries.retain(|v| v.tags.contains(&tag_filter));
+    }
+
     if vps_entries.is_empty() {
         print_warn!("⚠️  No VPS entries match the specified filters.");
         return Ok(());
@@ -99,6 +115,11 @@ fn list_vps_entries(vps_entries: &[VpsEntry]) {
             username: vps.effective_user(),
             host: vps.host.clone(),
             port: vps.effective_port().to_string(),
+            tags: if vps.tags.is_empty() {
+                "-".into()
+            } else {
+                vps.tags.join(", ")
+            },
         })
         .collect();
 
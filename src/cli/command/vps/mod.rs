@@ -3,15 +3,30 @@
 
 pub mod add;
 pub mod connect;
+pub mod copy;
 pub mod delete;
+pub mod facts;
 pub mod list;
+pub mod provision;
 pub mod script;
+pub mod ssh_config;
+pub mod status;
+pub mod tunnel;
 pub mod update;
 
 use clap::{Args, Subcommand};
+use dialoguer::{Select, theme::ColorfulTheme};
+use std::{io, process::ExitCode};
 
 use crate::{
-    cli::print_subcommand_help, config::TranquilityConfig, log_error, log_info, models::vps,
+    cli::print_subcommand_help,
+    config::TranquilityConfig,
+    log_error, log_info,
+    models::vps::{
+        self,
+        json::{VpsConfig, VpsEntry},
+    },
+    print_warn,
 };
 
 #[derive(Args, Debug)]
@@ -29,33 +44,60 @@ pub enum VpsSubcommand {
     Add(add::VpsAddCommand),
     List(list::VpsListCommand),
     Connect(connect::VpsConnectCommand),
+    Copy(copy::VpsCopyCommand),
     Update(update::VpsUpdateCommand),
     Delete(delete::VpsDeleteCommand),
     Script(script::VpsScriptCommand),
+    /// Write the VPS list to ~/.ssh/config as Host entries
+    ExportSshConfig(ssh_config::VpsExportSshConfigCommand),
+    /// Add Host entries from ~/.ssh/config to the VPS list
+    ImportSshConfig(ssh_config::VpsImportSshConfigCommand),
+    /// Show the OS, hardware and package managers of VPS hosts
+    Facts(facts::VpsFactsCommand),
+    /// Install server apps from the catalog on a VPS over ssh
+    Provision(provision::VpsProvisionCommand),
+    /// Check that VPS hosts are reachable and accept ssh logins
+    Status(status::VpsStatusCommand),
+    /// Start, list and stop background port forwards
+    Tunnel(tunnel::VpsTunnelCommand),
 }
 
-pub fn handle_vps_command(cmd: VpsCommand, dry_run: bool) {
+pub fn handle_vps_command(cmd: VpsCommand, dry_run: bool) -> ExitCode {
     if cmd.schema {
         vps::vps_config_schema(); // ← now actually calls the function
-        return;
+        return ExitCode::SUCCESS;
     }
 
     if cmd.delete {
         if let Err(e) = delete::confirm_and_delete_vps_config(dry_run) {
             log_error!("delete", "vps", &format!("❌ Failed to delete config: {e}"));
         }
-        return;
+        return ExitCode::SUCCESS;
     }
 
     match cmd.command {
         Some(VpsSubcommand::Add(add)) => add::vps_command_add(add, dry_run),
         Some(VpsSubcommand::List(list)) => list::vps_command_list(list, dry_run),
         Some(VpsSubcommand::Connect(connect)) => connect::vps_command_connect(connect, dry_run),
+        Some(VpsSubcommand::Copy(copy)) => return copy::vps_command_copy(copy, dry_run),
         Some(VpsSubcommand::Update(update)) => update::vps_command_update(update, dry_run),
         Some(VpsSubcommand::Delete(delete)) => delete::vps_command_delete(delete, dry_run),
-        Some(VpsSubcommand::Script(script)) => script::vps_command_script(script, dry_run),
+        Some(VpsSubcommand::Script(script)) => return script::vps_command_script(script, dry_run),
+        Some(VpsSubcommand::ExportSshConfig(export)) => {
+            ssh_config::vps_command_export_ssh_config(export, dry_run)
+        }
+        Some(VpsSubcommand::ImportSshConfig(import)) => {
+            ssh_config::vps_command_import_ssh_config(import, dry_run)
+        }
+        Some(VpsSubcommand::Facts(facts)) => return facts::vps_command_facts(facts, dry_run),
+        Some(VpsSubcommand::Provision(provision)) => {
+            return provision::vps_command_provision(provision, dry_run);
+        }
+        Some(VpsSubcommand::Status(status)) => return status::vps_command_status(status, dry_run),
+        Some(VpsSubcommand::Tunnel(tunnel)) => return tunnel::vps_command_tunnel(tunnel, dry_run),
         None => print_subcommand_help("vps"),
     }
+    ExitCode::SUCCESS
 }
 
 /// Fixes and updates the VPS config file in place, if needed
@@ -78,3 +120,46 @@ pub fn fix_vps() -> std::io::Result<()> {
 
     Ok(())
 }
+
+/// Finds the entry with `id`, or asks the user to pick one.
+///
+/// Returns `None` after warning when there are no entries or no match.
+pub fn select_vps<'a>(
+    config: &'a VpsConfig,
+    id: Option<&str>,
+    prompt: &str,
+) -> io::Result<Option<&'a VpsEntry>> {
+    if config.vps.is_empty() {
+        print_warn!("⚠️ No VPS entries found in your configuration.");
+        return Ok(None);
+    }
+
+    let vps = match id {
+        Some(id) => config.vps.iter().find(|v| v.id.as_deref() == Some(id)),
+        None => {
+            let options: Vec<String> = config
+                .vps
+                .iter()
+                .map(|v| {
+                    let user = v.effective_user();
+                    let name = v.name.clone().unwrap_or_else(|| "-".into());
+                    format!("{}@{} ({})", user, v.host, name)
+                })
+                .collect();
+
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt(prompt)
+                .items(&options)
+                .default(0)
+                .interact()
+                .map_err(|e| io::Error::other(format!("Prompt failed: {e}")))?;
+
+            Some(&config.vps[selection])
+        }
+    };
+
+    if vps.is_none() {
+        print_warn!("❌ No VPS found with the specified ID.");
+    }
+    Ok(vps)
+}
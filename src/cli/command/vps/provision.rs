@@ -0,0 +1,253 @@
+// Module: Command/Vps/Provision
+// Location: cli/src/command/vps/provision.rs
+
+use clap::Args;
+use std::{
+    io,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    cli::command::vps::{facts::facts_dir, select_vps},
+    config::TranquilityConfig,
+    core::{
+        report::{InstallReport, ReportStatus},
+        shell::{ShellCommand, output::Stream},
+    },
+    log_error, log_info, log_warn,
+    models::{
+        application::get_apps,
+        category::Category,
+        vps::{
+            facts::{self, RemoteFacts},
+            json::VpsConfig,
+            provision::{
+                install_script, no_method_reason, presence_script, print_install_plan,
+                remote_os_type, select_remote, server_apps,
+            },
+            ssh,
+        },
+    },
+    print_info, print_warn,
+};
+
+/// How long checking whether an app is installed may take once connected.
+const PRESENCE_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Args, Debug)]
+pub struct VpsProvisionCommand {
+    #[arg(long)]
+    id: Option<String>,
+    /// App to install, by id or name; repeatable
+    #[arg(long)]
+    app: Vec<String>,
+    /// Install the server apps in this category; repeatable
+    #[arg(long, value_enum, ignore_case = true)]
+    category: Vec<Category>,
+    /// Install without asking for each app
+    #[arg(short, long)]
+    yes: bool,
+    /// Gather facts again instead of using the cached ones
+    #[arg(long)]
+    refresh_facts: bool,
+}
+
+pub fn vps_command_provision(cmd: VpsProvisionCommand, dry_run: bool) -> ExitCode {
+    match provision(cmd, dry_run) {
+        Ok(report) => {
+            report.print_summary();
+            let cfg = TranquilityConfig::load_once();
+            match report.write_json(&cfg.log_directory) {
+                Ok(path) => print_info!("📄 Provision report written to {}", path.display()),
+                Err(e) => log_error!(
+                    "report",
+                    "provision",
+                    &format!("❌ Failed to write report: {e}")
+                ),
+            }
+            if report.has_failures() {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Err(e) => {
+            log_error!("provision", "vps", &format!("❌ Provisioning failed: {e}"));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn provision(cmd: VpsProvisionCommand, dry_run: bool) -> io::Result<InstallReport> {
+    let config = TranquilityConfig::load_once();
+    let mut vps_config = VpsConfig::load_from_file(&config.vps_file)?;
+    vps_config.fix();
+
+    let Some(vps) = select_vps(
+        &vps_config,
+        cmd.id.as_deref(),
+        "🏗️ Select a VPS to provision",
+    )?
+    else {
+        return Ok(InstallReport::new("provision", "none", dry_run));
+    };
+    let vps_id = vps.effective_id();
+    let mut report = InstallReport::new("provision", vps_id, dry_run);
+
+    let catalog = get_apps().applications;
+    let apps = server_apps(&catalog, &cmd.app, &cmd.category);
+    if let Some(name) = cmd.app.iter().find(|name| {
+        !apps.iter().any(|app| {
+            app.id
+                .as_ref()
+                .is_some_and(|id| id.eq_ignore_ascii_case(name))
+                || app.name.eq_ignore_ascii_case(name)
+        })
+    }) {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no server compatible app named '{name}'"),
+        ));
+    }
+    if apps.is_empty() {
+        print_warn!("⚠️ No server compatible apps match.");
+        return Ok(report);
+    }
+
+    let dir = facts_dir()?;
+    let facts = match RemoteFacts::load(&dir, vps_id) {
+        Some(facts) if !cmd.refresh_facts => facts,
+        cached if dry_run => {
+            print_info!("(dry run) Would gather facts from {}", vps_id);
+            let Some(facts) = cached else {
+                print_warn!(
+                    "⚠️ No cached facts for {}; run `vps facts` first to plan the installs.",
+                    vps_id
+                );
+                return Ok(report);
+            };
+            facts
+        }
+        _ => {
+            print_info!("🔎 Gathering facts from {}...", vps_id);
+            let facts = facts::gather(vps, &vps_config.vps)?;
+            facts.save(&dir)?;
+            facts
+        }
+    };
+    let os = remote_os_type(&facts).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!(
+                "unsupported remote OS: {}",
+                facts.os.as_deref().unwrap_or("unknown")
+            ),
+        )
+    })?;
+    print_info!("🐧 {} runs {}", vps_id, os);
+
+    let args = ssh::ssh_args(vps, &vps_config.vps)?;
+    let mut probe_args = ssh::batch_args(ssh::CONNECT_TIMEOUT);
+    probe_args.extend(args.iter().cloned());
+    let destination = ssh::destination(vps);
+    let root = vps.effective_user() == "root";
+
+    for app in apps {
+        let Some(selection) = select_remote(app, &facts, os) else {
+            let reason = no_method_reason(app, &facts, os);
+            print_warn!("No valid install method found for {}: {}", app.name, reason);
+            log_info!("provision", &app.name, &reason);
+            report.record(&app.name, ReportStatus::NoMethod, None, Some(reason));
+            continue;
+        };
+
+        let presence = presence_script(&selection);
+        if dry_run && let Some(ref check) = presence {
+            print_info!(
+                "(dry run) Would skip {} if `{}` succeeds on {}",
+                app.name,
+                check.script,
+                vps_id
+            );
+        }
+        let installed = !dry_run
+            && presence.is_some_and(|check| {
+                ShellCommand::from_remote_script(&destination, &probe_args, &check.script, false)
+                    .with_timeout(Some(PRESENCE_TIMEOUT))
+                    .capture()
+                    .is_ok_and(|(status, output)| {
+                        status.success() && check.matches_pin(&output.text(Stream::Stdout))
+                    })
+            });
+        if installed {
+            print_info!("Skipping {}: already installed", app.name);
+            report.record(
+                &app.name,
+                ReportStatus::Skipped,
+                None,
+                Some("already installed".into()),
+            );
+            continue;
+        }
+
+        if !cmd.yes && !dry_run && !app.prompt_install() {
+            print_info!("Skipping installation of {}", app.name);
+            report.record(
+                &app.name,
+                ReportStatus::Skipped,
+                None,
+                Some("declined".into()),
+            );
+            continue;
+        }
+
+        let start = Instant::now();
+        let outcome = if dry_run {
+            print_install_plan(&selection, root)
+        } else {
+            install_script(&selection, root).and_then(|install| {
+                // sudo prompts for a password on a terminal, so one is
+                // forced and the prompt is shown as it arrives
+                let mut ssh_args = args.clone();
+                if install.uses_sudo {
+                    ssh_args.insert(0, "-tt".into());
+                }
+                let command = ShellCommand::from_remote_script(
+                    &destination,
+                    &ssh_args,
+                    &install.script,
+                    false,
+                )
+                .with_label(app.id.as_deref().unwrap_or(&app.name));
+                if install.uses_sudo {
+                    command.run(false)
+                } else {
+                    command.run_verbose(false)
+                }
+            })
+        };
+        match outcome {
+            Ok(()) => {
+                log_info!("provision", &app.name, &format!("installed on {vps_id}"));
+                report.record(
+                    &app.name,
+                    ReportStatus::Installed,
+                    Some(start.elapsed()),
+                    None,
+                );
+            }
+            Err(e) => {
+                log_warn!("provision", &app.name, &format!("failed on {vps_id}: {e}"));
+                report.record(
+                    &app.name,
+                    ReportStatus::Failed,
+                    Some(start.elapsed()),
+                    Some(e.to_string()),
+                );
+            }
+        }
+    }
+
+    Ok(report)
+}
@@ -2,79 +2,81 @@
 // Location: cli/src/command/vps/script.rs
 
 use clap::Args;
-use dialoguer::{Select, theme::ColorfulTheme};
-use std::{fs, io, path::Path};
+use std::{fs, io, path::Path, process::ExitCode, time::Duration};
 
 use crate::{
+    cli::command::vps::select_vps,
     config::TranquilityConfig,
-    core::shell::script_runner::{ScriptSource, ShellScriptRunner},
+    core::shell::{
+        ShellCommand,
+        script_runner::{ScriptSource, ShellScriptRunner},
+    },
     log_error,
-    models::vps::json::VpsConfig,
-    print_warn,
+    models::vps::{
+        fleet::{self, Selector},
+        json::VpsConfig,
+        ssh,
+    },
+    print_info, print_warn,
 };
 use shellexpand::tilde;
 
 #[derive(Args, Debug)]
 pub struct VpsScriptCommand {
+    /// VPS to run on; repeat to run on several
     #[arg(long)]
-    id: Option<String>,
+    id: Vec<String>,
+    /// Run on every VPS with this tag; repeatable
+    #[arg(long)]
+    tag: Vec<String>,
+    /// Run on every VPS
+    #[arg(long, conflicts_with_all = ["id", "tag"])]
+    all: bool,
+    /// How many VPS to run on at once
+    #[arg(long, short = 'j', default_value_t = 4)]
+    parallel: usize,
+    /// Kill the script on a VPS after this many seconds
+    #[arg(long)]
+    timeout: Option<u64>,
     #[arg(long)]
     inline: Option<String>,
     #[arg(long)]
     file: Option<String>,
 }
 
-pub fn vps_command_script(cmd: VpsScriptCommand, dry_run: bool) {
-    if let Err(e) = run_vps_script(cmd, dry_run) {
+pub fn vps_command_script(cmd: VpsScriptCommand, dry_run: bool) -> ExitCode {
+    run_vps_script(cmd, dry_run).unwrap_or_else(|e| {
         log_error!(
             "script",
             "vps",
             &format!("❌ Failed to run VPS script: {e}")
         );
-    }
+        ExitCode::FAILURE
+    })
 }
 
-fn run_vps_script(cmd: VpsScriptCommand, dry_run: bool) -> io::Result<()> {
+fn run_vps_script(cmd: VpsScriptCommand, dry_run: bool) -> io::Result<ExitCode> {
     let config = TranquilityConfig::load_once();
     let mut vps_config = VpsConfig::load_from_file(&config.vps_file)?;
     vps_config.fix();
 
-    // STEP 1: Resolve VPS entry
-    let vps = match &cmd.id {
-        Some(id) => vps_config.vps.iter().find(|v| v.id.as_deref() == Some(id)),
-        None => {
-            if vps_config.vps.is_empty() {
-                print_warn!("⚠️ No VPS entries found in your configuration.");
-                return Ok(());
-            }
-
-            let options: Vec<String> = vps_config
-                .vps
-                .iter()
-                .map(|v| {
-                    let user = v.effective_user();
-                    let name = v.name.clone().unwrap_or_else(|| "-".into());
-                    format!("{}@{} ({})", user, v.host, name)
-                })
-                .collect();
-
-            let selection = Select::with_theme(&ColorfulTheme::default())
-                .with_prompt("💻 Select a VPS to run the script on")
-                .items(&options)
-                .default(0)
-                .interact()
-                .map_err(|e| io::Error::other(format!("Prompt failed: {e}")))?;
-
-            Some(&vps_config.vps[selection])
-        }
+    let selector = Selector {
+        ids: cmd.id.clone(),
+        tags: cmd.tag.clone(),
+        all: cmd.all,
     };
+    if selector.is_fleet() {
+        return run_fleet_script(cmd, &selector, &vps_config, dry_run);
+    }
 
-    let vps = match vps {
-        Some(v) => v,
-        None => {
-            print_warn!("❌ No VPS found with the specified ID.");
-            return Ok(());
-        }
+    // STEP 1: Resolve VPS entry
+    let Some(vps) = select_vps(
+        &vps_config,
+        cmd.id.first().map(String::as_str),
+        "💻 Select a VPS to run the script on",
+    )?
+    else {
+        return Ok(ExitCode::SUCCESS);
     };
 
     // STEP 2: Resolve script source: inline > file > post_connect_script
@@ -85,13 +87,14 @@ fn run_vps_script(cmd: VpsScriptCommand, dry_run: bool) -> io::Result<()> {
                 ScriptSource::Inline(script_str.clone())
             } else {
                 print_warn!("⚠️ No script provided via --inline, --file, or VPS config. Aborting.");
-                return Ok(());
+                return Ok(ExitCode::SUCCESS);
             }
         }
     };
 
     // STEP 3: Prepare and run script
-    let remote = format!("{}@{}", vps.effective_user(), vps.host);
+    let remote = ssh::destination(vps);
+    let ssh_args = ssh::ssh_args(vps, &vps_config.vps)?;
 
     let script_content = match &script {
         ScriptSource::Inline(content) => content.clone(),
@@ -102,12 +105,87 @@ fn run_vps_script(cmd: VpsScriptCommand, dry_run: bool) -> io::Result<()> {
         script: script_content,
         source: script,
         remote: Some(remote),
+        ssh_args,
         use_sudo: false,
         dry_run,
     };
 
-    runner.run_verbose();
-    Ok(())
+    runner.run_verbose().map(|()| ExitCode::SUCCESS)
+}
+
+/// Runs the script on every selected VPS, `--parallel` at a time, then
+/// prints a summary. Fails if any VPS failed.
+fn run_fleet_script(
+    cmd: VpsScriptCommand,
+    selector: &Selector,
+    vps_config: &VpsConfig,
+    dry_run: bool,
+) -> io::Result<ExitCode> {
+    let targets = selector.select(&vps_config.vps)?;
+    let script = match resolve_script(cmd.inline, cmd.file)? {
+        Some(ScriptSource::Inline(content)) => Some(content),
+        Some(ScriptSource::File(path)) => Some(fs::read_to_string(path)?),
+        None => None,
+    };
+
+    // Without --inline or --file each VPS runs its own post_connect_script
+    let mut jobs = Vec::new();
+    for vps in targets {
+        match script.as_ref().or(vps.post_connect_script.as_ref()) {
+            Some(script) => jobs.push((vps, script)),
+            None => print_warn!(
+                "⚠️ Skipping {}: no script provided and no post_connect_script set.",
+                vps.effective_id()
+            ),
+        }
+    }
+    if jobs.is_empty() {
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    print_info!(
+        "📜 Running script on {} VPS ({} at a time){}",
+        jobs.len(),
+        cmd.parallel.max(1),
+        if dry_run { " (dry-run)" } else { "" }
+    );
+
+    if dry_run {
+        for (vps, script) in jobs {
+            let ssh_args = ssh::ssh_args(vps, &vps_config.vps)?;
+            ShellCommand::from_remote_script(&ssh::destination(vps), &ssh_args, script, false)
+                .dry_run();
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let results = fleet::run_parallel(&jobs, cmd.parallel, |(vps, script)| {
+        fleet::run_script_on(
+            vps,
+            &vps_config.vps,
+            script,
+            false,
+            cmd.timeout.map(Duration::from_secs),
+        )
+    });
+    fleet::print_summary("script", &results);
+
+    for result in results.iter().filter(|r| !r.success()) {
+        log_error!(
+            "script",
+            &result.id,
+            &result.error.clone().unwrap_or_else(|| format!(
+                "exited with status code {}",
+                result.code.unwrap_or(-1)
+            )),
+            result.duration.as_secs_f64()
+        );
+    }
+    if results.iter().any(|r| !r.success()) {
+        Ok(ExitCode::FAILURE)
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
 }
 
 fn resolve_script(
@@ -0,0 +1,130 @@
+// Module: Command/Vps/SshConfig
+// Location: cli/src/command/vps/ssh_config.rs
+
+use clap::Args;
+use std::{fs, io, path::PathBuf};
+
+use crate::{
+    config::TranquilityConfig,
+    core::expand_home,
+    log_error, log_info,
+    models::vps::{
+        json::VpsConfig,
+        ssh_config::{merge_block, parse_hosts, render_block},
+    },
+    print_info, print_success, print_warn,
+};
+
+#[derive(Args, Debug)]
+pub struct VpsExportSshConfigCommand {
+    /// ssh config file to update
+    #[arg(long, default_value = "~/.ssh/config")]
+    file: String,
+}
+
+#[derive(Args, Debug)]
+pub struct VpsImportSshConfigCommand {
+    /// ssh config file to read
+    #[arg(long, default_value = "~/.ssh/config")]
+    file: String,
+}
+
+pub fn vps_command_export_ssh_config(cmd: VpsExportSshConfigCommand, dry_run: bool) {
+    if let Err(e) = export_ssh_config(&cmd.file, dry_run) {
+        log_error!(
+            "export-ssh-config",
+            "vps",
+            &format!("❌ Failed to export ssh config: {e}")
+        );
+    }
+}
+
+pub fn vps_command_import_ssh_config(cmd: VpsImportSshConfigCommand, dry_run: bool) {
+    if let Err(e) = import_ssh_config(&cmd.file, dry_run) {
+        log_error!(
+            "import-ssh-config",
+            "vps",
+            &format!("❌ Failed to import ssh config: {e}")
+        );
+    }
+}
+
+fn export_ssh_config(file: &str, dry_run: bool) -> io::Result<()> {
+    let config = TranquilityConfig::load_once();
+    let mut vps_config = VpsConfig::load_from_file(&config.vps_file)?;
+    vps_config.fix();
+
+    let path = PathBuf::from(expand_home(file));
+    let existing = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+
+    let block = render_block(&vps_config.vps);
+    if dry_run {
+        print_info!("(dry run) Would write to {}:", path.display());
+        println!("{block}");
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, merge_block(&existing, &block))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+    }
+
+    print_success!(
+        "✅ Wrote {} host(s) to {}",
+        vps_config.vps.len(),
+        path.display()
+    );
+    log_info!("export-ssh-config", "vps", "success");
+    Ok(())
+}
+
+fn import_ssh_config(file: &str, dry_run: bool) -> io::Result<()> {
+    let config = TranquilityConfig::load_once();
+    let mut vps_config = VpsConfig::load_from_file(&config.vps_file)?;
+    vps_config.fix();
+
+    let path = PathBuf::from(expand_home(file));
+    let hosts = parse_hosts(&fs::read_to_string(&path)?);
+
+    let mut added = 0;
+    for host in hosts {
+        let vps = host.clone().into_vps(&vps_config.vps);
+        // Hosts we exported, or that point at a known server, are skipped
+        let known = vps_config.vps.iter().any(|v| {
+            v.id.as_deref() == Some(host.alias.as_str())
+                || v.id == vps.id
+                || (v.host == vps.host
+                    && v.effective_user() == vps.effective_user()
+                    && v.effective_port() == vps.effective_port())
+        });
+        if known {
+            print_warn!("⚠️ Skipping {}: already in the VPS list", host.alias);
+            continue;
+        }
+
+        print_info!("➕ {} → {}@{}", host.alias, vps.effective_user(), vps.host);
+        vps_config.push(vps);
+        added += 1;
+    }
+
+    if dry_run {
+        print_info!("(dry run) {} VPS entry(s) not saved.", added);
+        return Ok(());
+    }
+
+    if added > 0 {
+        vps_config.save_to_file(&config.vps_file)?;
+    }
+    print_success!("✅ Imported {} host(s) from {}", added, path.display());
+    log_info!("import-ssh-config", "vps", &format!("imported {added}"));
+    Ok(())
+}
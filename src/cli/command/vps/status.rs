@@ -0,0 +1,148 @@
+// Module: Command/Vps/Status
+// Location: cli/src/command/vps/status.rs
+
+use clap::Args;
+use colored::Colorize;
+use std::{io, process::ExitCode, time::Duration};
+use tabled::{Table, Tabled, settings::Style};
+
+use crate::{
+    config::TranquilityConfig,
+    log_error,
+    models::vps::{
+        fleet::{self, Selector},
+        json::{VpsConfig, VpsEntry},
+        status::{self, VpsStatus},
+    },
+    print_info,
+};
+
+#[derive(Args, Debug)]
+pub struct VpsStatusCommand {
+    /// VPS to check; repeatable. Every VPS is checked by default
+    #[arg(long)]
+    id: Vec<String>,
+    /// Check every VPS with this tag; repeatable
+    #[arg(long)]
+    tag: Vec<String>,
+    /// Also check that key auth works with a non-interactive ssh login
+    #[arg(long)]
+    auth: bool,
+    /// Print the results as JSON
+    #[arg(long)]
+    json: bool,
+    /// Seconds to wait for each connection
+    #[arg(long, default_value_t = 5)]
+    timeout: u64,
+}
+
+#[derive(Tabled)]
+struct StatusDisplay {
+    #[tabled(rename = "VPS")]
+    id: String,
+    #[tabled(rename = "Address")]
+    address: String,
+    #[tabled(rename = "Reachable")]
+    reachable: String,
+    #[tabled(rename = "Latency")]
+    latency: String,
+    #[tabled(rename = "Auth")]
+    auth: String,
+    #[tabled(rename = "Banner / Error")]
+    details: String,
+}
+
+pub fn vps_command_status(cmd: VpsStatusCommand, dry_run: bool) -> ExitCode {
+    vps_status(cmd, dry_run).unwrap_or_else(|e| {
+        log_error!("status", "vps", &format!("❌ Failed to check VPS: {e}"));
+        ExitCode::FAILURE
+    })
+}
+
+/// Fails when any checked VPS is unhealthy, so scripts can rely on it.
+fn vps_status(cmd: VpsStatusCommand, dry_run: bool) -> io::Result<ExitCode> {
+    let config = TranquilityConfig::load_once();
+    let mut vps_config = VpsConfig::load_from_file(&config.vps_file)?;
+    vps_config.fix();
+
+    let selector = Selector {
+        all: cmd.id.is_empty() && cmd.tag.is_empty(),
+        ids: cmd.id,
+        tags: cmd.tag,
+    };
+    let targets = selector.select(&vps_config.vps)?;
+
+    if dry_run {
+        for vps in targets {
+            print_info!(
+                "(dry run) Would probe {}:{}{}",
+                vps.host,
+                vps.effective_port(),
+                if cmd.auth { " and check ssh auth" } else { "" }
+            );
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let timeout = Duration::from_secs(cmd.timeout.max(1));
+    let results = check_all(&targets, &vps_config, timeout, cmd.auth);
+
+    if cmd.json {
+        let json = serde_json::to_string_pretty(&results)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        println!("{json}");
+    } else {
+        print_table(&results);
+    }
+
+    if results.iter().all(VpsStatus::healthy) {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+/// Probes `targets` a few at a time, keeping their order.
+pub fn check_all(
+    targets: &[&VpsEntry],
+    vps_config: &VpsConfig,
+    timeout: Duration,
+    auth: bool,
+) -> Vec<VpsStatus> {
+    fleet::run_parallel(targets, 8, |vps| {
+        status::probe(vps, &vps_config.vps, timeout, auth)
+    })
+}
+
+fn yes_no(value: Option<bool>) -> String {
+    match value {
+        Some(true) => "yes".green().to_string(),
+        Some(false) => "no".red().to_string(),
+        None => "-".into(),
+    }
+}
+
+fn print_table(results: &[VpsStatus]) {
+    let rows: Vec<StatusDisplay> = results
+        .iter()
+        .map(|s| StatusDisplay {
+            id: s.id.clone(),
+            address: format!("{}:{}", s.host, s.port),
+            reachable: yes_no(s.reachable),
+            latency: s
+                .latency_ms
+                .map(|ms| format!("{ms:.0}ms"))
+                .unwrap_or_else(|| "-".into()),
+            auth: yes_no(s.auth),
+            details: s
+                .error
+                .clone()
+                .or_else(|| s.banner.clone())
+                .unwrap_or_else(|| "-".into()),
+        })
+        .collect();
+
+    let mut table = Table::new(rows);
+    table.with(Style::modern());
+    println!("{table}");
+}
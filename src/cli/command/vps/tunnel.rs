@@ -0,0 +1,308 @@
+// Module: Command/Vps/Tunnel
+// Location: cli/src/command/vps/tunnel.rs
+
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use std::{
+    fs::{self, File},
+    io,
+    path::PathBuf,
+    process::{Command, ExitCode, Stdio},
+    thread,
+    time::Duration,
+};
+use tabled::{Table, Tabled, settings::Style};
+
+use crate::{
+    cli::command::vps::select_vps,
+    config::TranquilityConfig,
+    log_error, log_info,
+    models::vps::{
+        json::{VpsConfig, VpsEntry},
+        tunnel::{TunnelRecord, TunnelSpec, kill_process, tunnel_command},
+    },
+    print_info, print_success, print_warn,
+};
+
+/// How long a new tunnel must stay up to count as started.
+const STARTUP_GRACE: Duration = Duration::from_millis(1500);
+
+#[derive(Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct VpsTunnelCommand {
+    #[command(subcommand)]
+    command: Option<TunnelSubcommand>,
+    #[arg(long)]
+    id: Option<String>,
+    /// Start only this named tunnel, or name an ad-hoc one
+    #[arg(long)]
+    name: Option<String>,
+    /// Local forward (`ssh -L`), e.g. 5433:localhost:5432
+    #[arg(long)]
+    local: Vec<String>,
+    /// Remote forward (`ssh -R`)
+    #[arg(long)]
+    remote: Vec<String>,
+    /// SOCKS proxy port (`ssh -D`)
+    #[arg(long)]
+    dynamic: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+enum TunnelSubcommand {
+    /// Show started tunnels and whether they are still running
+    List,
+    /// Stop tunnels of a VPS, or all of them
+    Stop {
+        #[arg(long, required_unless_present = "all")]
+        id: Option<String>,
+        /// Stop only this tunnel
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(Tabled)]
+struct TunnelDisplay {
+    #[tabled(rename = "VPS")]
+    vps_id: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "PID")]
+    pid: u32,
+    #[tabled(rename = "Forwards")]
+    forwards: String,
+    #[tabled(rename = "Started")]
+    started_at: String,
+    #[tabled(rename = "Status")]
+    status: String,
+}
+
+pub fn vps_command_tunnel(cmd: VpsTunnelCommand, dry_run: bool) -> ExitCode {
+    let result = match cmd.command {
+        Some(TunnelSubcommand::List) => list_tunnels(),
+        Some(TunnelSubcommand::Stop { id, name, all }) => {
+            stop_tunnels(id.as_deref(), name.as_deref(), all, dry_run)
+        }
+        None => start_tunnels(cmd, dry_run),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            log_error!("tunnel", "vps", &format!("❌ Tunnel failed: {e}"));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn tunnels_dir() -> io::Result<PathBuf> {
+    Ok(TranquilityConfig::config_dir()?.join("tunnels"))
+}
+
+/// The tunnels to start: the ad-hoc one from the flags, else the entry's
+/// named tunnels (or just `--name`), else the forwards set on the entry.
+fn resolve_specs(cmd: &VpsTunnelCommand, vps: &VpsEntry) -> io::Result<Vec<TunnelSpec>> {
+    if !cmd.local.is_empty() || !cmd.remote.is_empty() || cmd.dynamic.is_some() {
+        let name = cmd.name.clone().unwrap_or_else(|| {
+            cmd.local
+                .iter()
+                .chain(&cmd.remote)
+                .chain(&cmd.dynamic)
+                .map(|spec| spec.split(':').next().unwrap_or(spec))
+                .collect::<Vec<_>>()
+                .join("-")
+        });
+        return Ok(vec![TunnelSpec {
+            name,
+            local_forwards: cmd.local.clone(),
+            remote_forwards: cmd.remote.clone(),
+            dynamic_forward: cmd.dynamic.clone(),
+        }]);
+    }
+
+    let specs: Vec<TunnelSpec> = if vps.tunnels.is_empty() {
+        vec![TunnelSpec::from_entry(vps)]
+    } else {
+        vps.tunnels.clone()
+    };
+    let specs: Vec<TunnelSpec> = specs
+        .into_iter()
+        .filter(|s| cmd.name.as_ref().is_none_or(|name| &s.name == name))
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    if specs.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            match cmd.name {
+                Some(ref name) => format!("no tunnel named '{name}' on {}", vps.effective_id()),
+                None => format!(
+                    "{} defines no tunnels; pass --local, --remote or --dynamic",
+                    vps.effective_id()
+                ),
+            },
+        ));
+    }
+    Ok(specs)
+}
+
+fn start_tunnels(cmd: VpsTunnelCommand, dry_run: bool) -> io::Result<()> {
+    let config = TranquilityConfig::load_once();
+    let mut vps_config = VpsConfig::load_from_file(&config.vps_file)?;
+    vps_config.fix();
+
+    let Some(vps) = select_vps(
+        &vps_config,
+        cmd.id.as_deref(),
+        "🚇 Select a VPS to tunnel to",
+    )?
+    else {
+        return Ok(());
+    };
+    let dir = tunnels_dir()?;
+
+    for spec in resolve_specs(&cmd, vps)? {
+        let vps_id = vps.effective_id();
+        if let Some(record) = TunnelRecord::load(&dir, vps_id, &spec.name) {
+            if record.is_alive() {
+                print_warn!(
+                    "⚠️ Tunnel {} to {} is already running (pid {})",
+                    spec.name,
+                    vps_id,
+                    record.pid
+                );
+                continue;
+            }
+            record.remove(&dir)?;
+        }
+
+        let command = tunnel_command(vps, &vps_config.vps, &spec)?;
+        if dry_run {
+            command.dry_run();
+            continue;
+        }
+
+        fs::create_dir_all(&dir)?;
+        let log_path = TunnelRecord::log_path(&dir, vps_id, &spec.name);
+        let mut process = Command::new(&command.command);
+        process
+            .args(&command.args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(File::create(&log_path)?);
+        // Keep the tunnel out of the terminal's process group so Ctrl-C in
+        // this shell doesn't take it down
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            process.process_group(0);
+        }
+        let mut child = process.spawn()?;
+
+        thread::sleep(STARTUP_GRACE);
+        if let Some(status) = child.try_wait()? {
+            let log = fs::read_to_string(&log_path).unwrap_or_default();
+            let _ = fs::remove_file(&log_path);
+            return Err(io::Error::other(format!(
+                "tunnel {} exited with {}: {}",
+                spec.name,
+                status,
+                log.trim()
+            )));
+        }
+
+        let record = TunnelRecord {
+            vps_id: vps_id.to_string(),
+            name: spec.name.clone(),
+            pid: child.id(),
+            forwards: spec.forward_args(),
+            started_at: chrono::Local::now().to_rfc3339(),
+        };
+        record.save(&dir)?;
+        print_success!(
+            "🚇 Tunnel {} to {} running (pid {}): {}",
+            spec.name,
+            vps_id,
+            record.pid,
+            record.forwards.join(" ")
+        );
+        log_info!("tunnel", vps_id, &format!("started {}", spec.name));
+    }
+    Ok(())
+}
+
+fn list_tunnels() -> io::Result<()> {
+    let records = TunnelRecord::load_all(&tunnels_dir()?)?;
+    if records.is_empty() {
+        print_info!("No tunnels started.");
+        return Ok(());
+    }
+
+    let rows: Vec<TunnelDisplay> = records
+        .into_iter()
+        .map(|r| TunnelDisplay {
+            status: if r.is_alive() {
+                "running".green().to_string()
+            } else {
+                "dead".red().to_string()
+            },
+            forwards: r.forwards.join(" "),
+            vps_id: r.vps_id,
+            name: r.name,
+            pid: r.pid,
+            started_at: r.started_at,
+        })
+        .collect();
+
+    let mut table = Table::new(rows);
+    table.with(Style::rounded());
+    println!("{table}");
+    Ok(())
+}
+
+fn stop_tunnels(id: Option<&str>, name: Option<&str>, all: bool, dry_run: bool) -> io::Result<()> {
+    let dir = tunnels_dir()?;
+    let records: Vec<TunnelRecord> = TunnelRecord::load_all(&dir)?
+        .into_iter()
+        .filter(|r| all || id == Some(r.vps_id.as_str()))
+        .filter(|r| name.is_none_or(|n| n == r.name))
+        .collect();
+
+    if records.is_empty() {
+        print_warn!("⚠️ No matching tunnels.");
+        return Ok(());
+    }
+
+    for record in records {
+        if dry_run {
+            print_info!(
+                "(dry run) Would stop tunnel {} to {} (pid {})",
+                record.name,
+                record.vps_id,
+                record.pid
+            );
+            continue;
+        }
+
+        // A dead tunnel only leaves its record behind
+        if record.is_alive() {
+            kill_process(record.pid)?;
+            print_success!("🛑 Stopped tunnel {} to {}", record.name, record.vps_id);
+        } else {
+            print_info!(
+                "🧹 Removed dead tunnel {} to {}",
+                record.name,
+                record.vps_id
+            );
+        }
+        record.remove(&dir)?;
+        log_info!(
+            "tunnel",
+            &record.vps_id,
+            &format!("stopped {}", record.name)
+        );
+    }
+    Ok(())
+}
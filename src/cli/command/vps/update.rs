@@ -21,6 +21,12 @@ pub struct VpsUpdateCommand {
     port: Option<String>,
     #[arg(long = "private-key")]
     private_key: Option<String>,
+    /// Add a tag; repeatable
+    #[arg(long)]
+    tag: Vec<String>,
+    /// Remove a tag; repeatable
+    #[arg(long)]
+    untag: Vec<String>,
 }
 
 pub fn vps_command_update(cmd: VpsUpdateCommand, dry_run: bool) {
@@ -99,6 +105,19 @@ fn update_vps_entry(cmd: VpsUpdateCommand, dry_run: bool) -> io::Result<()> {
         changed = true;
     }
 
+    for tag in cmd.tag {
+        if !entry.tags.contains(&tag) {
+            entry.tags.push(tag);
+            changed = true;
+        }
+    }
+
+    if !cmd.untag.is_empty() {
+        let before = entry.tags.len();
+        entry.tags.retain(|t| !cmd.untag.contains(t));
+        changed |= entry.tags.len() != before;
+    }
+
     if !changed {
         print_warn!("⚠️ No updates were provided. Entry remains unchanged.");
         return Ok(());
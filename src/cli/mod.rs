@@ -2,8 +2,12 @@ pub mod command;
 
 use crate::{log_error, print_info};
 use clap::{CommandFactory, Error, Parser, Subcommand, error::ErrorKind};
+use std::process::ExitCode;
 
-use crate::{core::logger, models::system::SystemInfo};
+use crate::{
+    core::{logger, shell::output},
+    models::system::SystemInfo,
+};
 /// Tranquility CLI command line parser
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -12,11 +16,15 @@ pub struct TranquilityCommand {
     #[arg(long, action = clap::ArgAction::SetTrue)]
     pub debug: bool,
 
-    /// Show verbose output
+    /// Show what would be done without making changes
     #[arg(long, action = clap::ArgAction::SetTrue)]
     #[arg(long)]
     dry_run: bool,
 
+    /// Show a spinner instead of command output, printing it only on failure
+    #[arg(long, short, action = clap::ArgAction::SetTrue)]
+    pub quiet: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -40,17 +48,21 @@ pub enum Commands {
     List(command::list::ListCommand),
 }
 
-/// handle_args function
-pub fn handle_commands(commands: TranquilityCommand) {
+/// Runs the parsed command and returns the process exit code.
+pub fn handle_commands(commands: TranquilityCommand) -> ExitCode {
     if commands.command.is_none() {
         println!("{}\n", SystemInfo::new().to_pretty_string());
-        return;
+        return ExitCode::SUCCESS;
     }
 
     if commands.debug {
         logger::set_debug(true);
     }
 
+    if commands.quiet {
+        output::set_quiet(true);
+    }
+
     if commands.dry_run {
         print_info!("💡 Running in dry-run mode. No changes will be made.");
     }
@@ -66,14 +78,19 @@ pub fn handle_commands(commands: TranquilityCommand) {
 
         Some(Commands::Doctor(doctor)) => command::doctor::doctor_command(doctor, commands.dry_run),
 
-        Some(Commands::App(app)) => command::app::handle_app_command(app, commands.dry_run),
+        Some(Commands::App(app)) => {
+            return command::app::handle_app_command(app, commands.dry_run);
+        }
 
-        Some(Commands::Vps(vps)) => command::vps::handle_vps_command(vps, commands.dry_run),
+        Some(Commands::Vps(vps)) => {
+            return command::vps::handle_vps_command(vps, commands.dry_run);
+        }
 
         Some(Commands::List(list)) => command::list::handle_list_command(list, commands.dry_run),
 
         None => {}
     }
+    ExitCode::SUCCESS
 }
 
 pub fn handle_command_errors(err: Error) {
@@ -1,10 +1,14 @@
 pub mod schema;
 
-use crate::SUPPORTED_EXTS;
+use crate::{
+    SUPPORTED_EXTS,
+    core::{retry::RetryConfig, shell::privilege::Escalation},
+};
 use dirs::config_dir;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     fs,
     io::{self},
     path::{Path, PathBuf},
@@ -27,6 +31,21 @@ pub struct TranquilityConfig {
     pub vps_file: PathBuf,
     pub log_directory: PathBuf,
     pub log_output: LogOutput,
+    /// User-defined variables available to install steps as `{{name}}`
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+    /// Pinned sha256 of package manager installers, keyed by installer name
+    #[serde(default)]
+    pub installer_checksums: BTreeMap<String, String>,
+    /// How privileged commands are elevated: auto, sudo, doas, run0, pkexec or none
+    #[serde(default)]
+    pub escalation: Escalation,
+    /// Seconds after which a command without its own timeout is killed
+    #[serde(default)]
+    pub command_timeout: Option<u64>,
+    /// Retry policies for package managers, downloads and SSH
+    #[serde(default)]
+    pub retry: RetryConfig,
 }
 
 impl TranquilityConfig {
@@ -76,6 +95,11 @@ impl TranquilityConfig {
             vps_file: Self::default_file_path(&base_dir, "vps"),
             log_directory,
             log_output: LogOutput::Primary,
+            variables: BTreeMap::new(),
+            installer_checksums: BTreeMap::new(),
+            escalation: Escalation::default(),
+            command_timeout: None,
+            retry: RetryConfig::default(),
         })
     }
 
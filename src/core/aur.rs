@@ -0,0 +1,133 @@
+// Module: Core/Aur
+// Location: cli/src/core/aur.rs
+use std::io;
+
+use crate::{
+    core::shell::{
+        ShellCommand,
+        command::{command_exists, is_root},
+    },
+    print_info,
+};
+
+pub const AUR_BASE_URL: &str = "https://aur.archlinux.org";
+
+/// AUR helpers in order of preference.
+pub const AUR_HELPERS: [&str; 2] = ["paru", "yay"];
+
+/// The first installed AUR helper, if any.
+pub fn detect_helper() -> Option<&'static str> {
+    AUR_HELPERS
+        .into_iter()
+        .find(|helper| command_exists(helper))
+}
+
+/// Whether AUR packages can be built without a helper.
+pub fn can_build() -> bool {
+    command_exists("git") && command_exists("makepkg")
+}
+
+/// Installs AUR packages through a helper, or by cloning the package
+/// repository and running `makepkg` when no helper is available.
+#[derive(Debug, Clone)]
+pub struct AurBuilder {
+    base_url: String,
+    makepkg: Vec<String>,
+    helper: Option<String>,
+}
+
+impl Default for AurBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AurBuilder {
+    pub fn new() -> Self {
+        Self {
+            base_url: AUR_BASE_URL.to_string(),
+            makepkg: ["makepkg", "-si", "--noconfirm"].map(String::from).to_vec(),
+            helper: detect_helper().map(String::from),
+        }
+    }
+
+    /// Where package repositories are cloned from, `<base_url>/<pkg>.git`.
+    pub fn with_base_url(mut self, url: &str) -> Self {
+        self.base_url = url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Command run inside the cloned repository to build and install it.
+    pub fn with_makepkg<I, S>(mut self, cmd: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.makepkg = cmd.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Overrides the detected helper; `None` forces a helper-less build.
+    pub fn with_helper(mut self, helper: Option<&str>) -> Self {
+        self.helper = helper.map(String::from);
+        self
+    }
+
+    pub fn helper(&self) -> Option<&str> {
+        self.helper.as_deref()
+    }
+
+    pub fn repo_url(&self, package: &str) -> String {
+        format!("{}/{}.git", self.base_url, package)
+    }
+
+    /// Helper command that installs `package`, if a helper is configured.
+    pub fn helper_command(&self, package: &str) -> Option<ShellCommand> {
+        let helper = self.helper.as_deref()?;
+        Some(ShellCommand::new(helper).with_args(["-S", package, "--noconfirm", "--needed"]))
+    }
+
+    pub fn install(&self, package: &str, dry_run: bool) -> io::Result<()> {
+        if let Some(cmd) = self.helper_command(package) {
+            return cmd.run(dry_run);
+        }
+
+        // makepkg refuses to run as root; it calls sudo itself for -i
+        if is_root() {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!(
+                    "cannot build AUR package '{package}' as root; rerun as a regular user or install paru/yay"
+                ),
+            ));
+        }
+
+        self.build(package, dry_run)
+    }
+
+    /// Clones the package repository into a temp dir and runs `makepkg` there.
+    pub fn build(&self, package: &str, dry_run: bool) -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let checkout = dir.path().join(package);
+        print_info!("📦 Building {} from {}", package, self.repo_url(package));
+
+        ShellCommand::new("git")
+            .with_args([
+                "clone".into(),
+                "--depth".into(),
+                "1".into(),
+                self.repo_url(package),
+                checkout.display().to_string(),
+            ])
+            .run(dry_run)?;
+
+        let (cmd, args) = self
+            .makepkg
+            .split_first()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "empty makepkg command"))?;
+        ShellCommand::new(cmd)
+            .with_args(args.iter().cloned())
+            .with_cwd(Some(checkout))
+            .run(dry_run)
+    }
+}
@@ -0,0 +1,176 @@
+// Module: Core/Bootstrap
+// Location: cli/src/core/bootstrap.rs
+use std::{collections::BTreeMap, fs, io::Write, path::Path};
+
+use colored::Colorize;
+use sha2::{Digest, Sha256};
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+use crate::{
+    config::TranquilityConfig,
+    core::{retry, shell::ShellCommand},
+    print_info,
+};
+
+/// A package manager installer script fetched from its vendor.
+#[derive(Debug)]
+pub struct Installer {
+    /// Key used for `installer_checksums` in the config
+    pub name: &'static str,
+    pub url: &'static str,
+    /// Program and arguments that run the downloaded script
+    pub runner: &'static [&'static str],
+    /// File extension the runner expects, e.g. `.ps1`
+    pub extension: &'static str,
+    /// sha256 pinned in the binary, only set for immutable URLs
+    pub sha256: Option<&'static str>,
+}
+
+// Homebrew, Scoop and Chocolatey serve their installers from moving URLs,
+// so each one must be reviewed and pinned in the config before it runs.
+pub const HOMEBREW: Installer = Installer {
+    name: "homebrew",
+    url: "https://raw.githubusercontent.com/Homebrew/install/HEAD/install.sh",
+    runner: &["/bin/bash"],
+    extension: ".sh",
+    sha256: None,
+};
+
+// Versioned and immutable, so its digest belongs in the binary. It has not
+// been recorded yet; until it is, pin it in the config like the others.
+pub const NIX: Installer = Installer {
+    name: "nix",
+    url: "https://releases.nixos.org/nix/nix-2.24.9/install",
+    runner: &["sh"],
+    extension: ".sh",
+    sha256: None,
+};
+
+pub const SCOOP: Installer = Installer {
+    name: "scoop",
+    url: "https://raw.githubusercontent.com/ScoopInstaller/Install/master/install.ps1",
+    runner: &[
+        "powershell",
+        "-NoProfile",
+        "-ExecutionPolicy",
+        "Bypass",
+        "-File",
+    ],
+    extension: ".ps1",
+    sha256: None,
+};
+
+pub const CHOCOLATEY: Installer = Installer {
+    name: "choco",
+    url: "https://community.chocolatey.org/install.ps1",
+    runner: &[
+        "powershell",
+        "-NoProfile",
+        "-ExecutionPolicy",
+        "Bypass",
+        "-File",
+    ],
+    extension: ".ps1",
+    sha256: None,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum VerifyError {
+    #[error(
+        "no sha256 pinned for '{name}' (downloaded digest {actual}); review the script and add it to `installer_checksums` in the config"
+    )]
+    NotPinned { name: String, actual: String },
+    #[error("sha256 mismatch for '{name}': expected {expected}, got {actual}")]
+    Mismatch {
+        name: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Checks `actual` against the pinned digest, refusing unpinned scripts.
+pub fn verify_digest(name: &str, actual: &str, pinned: Option<&str>) -> Result<(), VerifyError> {
+    match pinned {
+        None => Err(VerifyError::NotPinned {
+            name: name.to_string(),
+            actual: actual.to_string(),
+        }),
+        Some(expected) if expected.trim().eq_ignore_ascii_case(actual) => Ok(()),
+        Some(expected) => Err(VerifyError::Mismatch {
+            name: name.to_string(),
+            expected: expected.trim().to_string(),
+            actual: actual.to_string(),
+        }),
+    }
+}
+
+impl Installer {
+    /// The digest from the config, falling back to the one in the binary.
+    pub fn pinned_digest<'a>(&'a self, checksums: &'a BTreeMap<String, String>) -> Option<&'a str> {
+        checksums.get(self.name).map(String::as_str).or(self.sha256)
+    }
+
+    /// Verifies an already downloaded script, returning its digest.
+    pub fn verify_file(&self, path: &Path, pinned: Option<&str>) -> std::io::Result<String> {
+        let digest = sha256_hex(&fs::read(path)?);
+        verify_digest(self.name, &digest, pinned).map_err(std::io::Error::other)?;
+        Ok(digest)
+    }
+
+    /// Downloads the script to a temp file, verifies it against the pinned
+    /// sha256 and only then runs it with `args`.
+    pub fn run(&self, args: &[&str], dry_run: bool) -> std::io::Result<()> {
+        let checksums = &TranquilityConfig::load_once().installer_checksums;
+        let pinned = self.pinned_digest(checksums);
+
+        if dry_run {
+            print_info!(
+                "Would download {} and run it if its sha256 is {}",
+                self.url,
+                pinned.unwrap_or("pinned")
+            );
+            return Ok(());
+        }
+
+        let body = retry::download(self.url)?;
+
+        let mut script = tempfile::Builder::new()
+            .prefix(&format!("{}-installer-", self.name))
+            .suffix(self.extension)
+            .tempfile()?;
+        script.write_all(&body)?;
+        script.flush()?;
+
+        let digest = self.verify_file(script.path(), pinned)?;
+        println!("📥 Source: {}", self.url.cyan());
+        println!("🔐 sha256: {} {}", digest.green(), "(verified)".green());
+
+        self.execute(&script, args)
+    }
+
+    fn execute(&self, script: &NamedTempFile, args: &[&str]) -> std::io::Result<()> {
+        let (program, runner_args) = self
+            .runner
+            .split_first()
+            .expect("installer runner is never empty");
+        let path = script.path().display().to_string();
+
+        ShellCommand::new(program)
+            .with_args(
+                runner_args
+                    .iter()
+                    .copied()
+                    .chain([path.as_str()])
+                    .chain(args.iter().copied()),
+            )
+            .run(false)
+    }
+}
@@ -1,7 +1,12 @@
+pub mod aur;
+pub mod bootstrap;
 pub mod font;
 pub mod logger;
 pub mod print;
+pub mod report;
+pub mod retry;
 pub mod shell;
+pub mod template;
 pub mod zip;
 
 pub fn expand_home(input: &str) -> String {
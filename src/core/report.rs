@@ -0,0 +1,141 @@
+// Module: Core/Report
+// Location: cli/src/core/report.rs
+use std::{fs, io, path::Path, path::PathBuf, time::Duration};
+
+use chrono::{Local, Utc};
+use colored::Colorize;
+use serde::Serialize;
+use strum::Display;
+use tabled::{Table, Tabled, settings::Style};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Display)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportStatus {
+    Installed,
+    Uninstalled,
+    Upgraded,
+    Skipped,
+    Failed,
+    #[strum(to_string = "No method")]
+    NoMethod,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportEntry {
+    pub app: String,
+    pub status: ReportStatus,
+    pub duration_secs: Option<f64>,
+    pub message: Option<String>,
+}
+
+/// Recap of an install run, printed as a table and saved as JSON.
+#[derive(Debug, Serialize)]
+pub struct InstallReport {
+    pub action: String,
+    pub target: String,
+    pub started_at: String,
+    pub dry_run: bool,
+    pub entries: Vec<ReportEntry>,
+}
+
+#[derive(Tabled)]
+struct DisplayEntry {
+    #[tabled(rename = "App")]
+    app: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Duration")]
+    duration: String,
+    #[tabled(rename = "Details")]
+    message: String,
+}
+
+impl InstallReport {
+    pub fn new(action: &str, target: &str, dry_run: bool) -> Self {
+        Self {
+            action: action.to_string(),
+            target: target.to_string(),
+            started_at: Utc::now().to_rfc3339(),
+            dry_run,
+            entries: vec![],
+        }
+    }
+
+    pub fn record(
+        &mut self,
+        app: &str,
+        status: ReportStatus,
+        duration: Option<Duration>,
+        message: Option<String>,
+    ) {
+        self.entries.push(ReportEntry {
+            app: app.to_string(),
+            status,
+            duration_secs: duration.map(|d| d.as_secs_f64()),
+            message,
+        });
+    }
+
+    pub fn count(&self, status: ReportStatus) -> usize {
+        self.entries.iter().filter(|e| e.status == status).count()
+    }
+
+    /// Whether any app failed or had no install method for the target.
+    pub fn has_failures(&self) -> bool {
+        self.count(ReportStatus::Failed) + self.count(ReportStatus::NoMethod) > 0
+    }
+
+    pub fn print_summary(&self) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let rows: Vec<DisplayEntry> = self
+            .entries
+            .iter()
+            .map(|e| DisplayEntry {
+                app: e.app.clone(),
+                status: match e.status {
+                    ReportStatus::Installed
+                    | ReportStatus::Uninstalled
+                    | ReportStatus::Upgraded => e.status.to_string().green().to_string(),
+                    ReportStatus::Skipped => e.status.to_string().blue().to_string(),
+                    ReportStatus::Failed => e.status.to_string().red().to_string(),
+                    ReportStatus::NoMethod => e.status.to_string().yellow().to_string(),
+                },
+                duration: e
+                    .duration_secs
+                    .map(|d| format!("{d:.2}s"))
+                    .unwrap_or_else(|| "-".into()),
+                message: e.message.clone().unwrap_or_else(|| "-".into()),
+            })
+            .collect();
+
+        let mut table = Table::new(rows);
+        table.with(Style::modern_rounded());
+        println!("\n📋 {} summary ({}):\n{}", self.action, self.target, table);
+        println!(
+            "{} succeeded, {} skipped, {} failed, {} without a method",
+            self.count(ReportStatus::Installed)
+                + self.count(ReportStatus::Uninstalled)
+                + self.count(ReportStatus::Upgraded),
+            self.count(ReportStatus::Skipped),
+            self.count(ReportStatus::Failed),
+            self.count(ReportStatus::NoMethod),
+        );
+    }
+
+    /// Writes the report as JSON into `dir`, returning the file path.
+    pub fn write_json(&self, dir: &Path) -> io::Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(format!(
+            "{}-report-{}.json",
+            self.action,
+            Local::now().format("%Y-%m-%d-%H%M%S")
+        ));
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(&path, json)?;
+        Ok(path)
+    }
+}
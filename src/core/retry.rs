@@ -0,0 +1,217 @@
+// Module: Core/Retry
+// Location: cli/src/core/retry.rs
+use std::{fmt::Display, thread, time::Duration};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{config::CONFIG, log_warn, print_warn};
+
+/// How often and how patiently a failed operation is retried.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first one
+    pub attempts: u32,
+    /// Delay before the first retry, in milliseconds
+    pub initial_delay_ms: u64,
+    /// Upper bound for any single delay, in milliseconds
+    pub max_delay_ms: u64,
+    /// Factor the delay grows by after each retry
+    pub multiplier: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            initial_delay_ms: 2_000,
+            max_delay_ms: 30_000,
+            multiplier: 2.0,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `retry`, counting from 1.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .max(1.0)
+            .powi(retry.saturating_sub(1) as i32);
+        let ms = (self.initial_delay_ms as f64 * factor).min(self.max_delay_ms as f64);
+        Duration::from_millis(ms as u64)
+    }
+}
+
+/// Retry policies per kind of operation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetryConfig {
+    /// Package manager commands, mostly waiting out dpkg/rpm locks held by
+    /// first-boot updates
+    pub package_manager: RetryPolicy,
+    /// HTTP downloads of fonts, installers, keys and repository files
+    pub download: RetryPolicy,
+    /// SSH connections to VPS hosts
+    pub ssh: RetryPolicy,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            package_manager: RetryPolicy {
+                attempts: 6,
+                initial_delay_ms: 5_000,
+                max_delay_ms: 60_000,
+                multiplier: 2.0,
+            },
+            download: RetryPolicy::default(),
+            ssh: RetryPolicy::default(),
+        }
+    }
+}
+
+impl RetryConfig {
+    /// The policies from the loaded config, or the defaults.
+    pub fn current() -> RetryConfig {
+        CONFIG
+            .get()
+            .map(|cfg| cfg.retry.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Output of package managers failing for reasons that go away on their own.
+const PACKAGE_MANAGER_TRANSIENT: &[&str] = &[
+    "could not get lock",
+    "unable to acquire the dpkg frontend lock",
+    "unable to lock the administration directory",
+    "is another process using it",
+    "waiting for cache lock",
+    "another app is currently holding the yum lock",
+    "waiting for process with pid",
+    "system management is locked",
+    "unable to lock database",
+];
+
+/// Output of network failures, shared by package managers and ssh.
+const NETWORK_TRANSIENT: &[&str] = &[
+    "temporary failure resolving",
+    "temporary failure in name resolution",
+    "could not resolve host",
+    "connection reset by peer",
+    "connection timed out",
+    "operation timed out",
+    "network is unreachable",
+    "502 bad gateway",
+    "503 service unavailable",
+    "504 gateway timeout",
+];
+
+/// ssh's own connection failures, as opposed to the remote command's.
+const SSH_TRANSIENT: &[&str] = &[
+    "connection refused",
+    "connection closed by",
+    "kex_exchange_identification",
+    "no route to host",
+    "could not resolve hostname",
+];
+
+fn mentions_any(output: &str, patterns: &[&str]) -> bool {
+    let output = output.to_lowercase();
+    patterns.iter().any(|p| output.contains(p))
+}
+
+/// Whether a failed package manager command is worth running again.
+pub fn package_manager_transient(_code: Option<i32>, output: &str) -> bool {
+    mentions_any(output, PACKAGE_MANAGER_TRANSIENT) || mentions_any(output, NETWORK_TRANSIENT)
+}
+
+/// Whether ssh failed to connect. ssh exits 255 for its own errors, so a
+/// remote command failing with a similar message is not retried.
+pub fn ssh_transient(code: Option<i32>, output: &str) -> bool {
+    code == Some(255)
+        && (mentions_any(output, SSH_TRANSIENT) || mentions_any(output, NETWORK_TRANSIENT))
+}
+
+/// Whether an HTTP request failed on the network or server side.
+pub fn http_transient(err: &reqwest::Error) -> bool {
+    if let Some(status) = err.status() {
+        return status.is_server_error() || status.as_u16() == 429;
+    }
+    err.is_connect() || err.is_timeout() || err.is_request() || err.is_body()
+}
+
+/// A retry policy paired with the check deciding which command failures are
+/// transient, given the exit code and the command's output.
+#[derive(Debug, Clone)]
+pub struct Retry {
+    pub policy: RetryPolicy,
+    pub is_transient: fn(Option<i32>, &str) -> bool,
+}
+
+impl Retry {
+    pub fn package_manager() -> Self {
+        Self {
+            policy: RetryConfig::current().package_manager,
+            is_transient: package_manager_transient,
+        }
+    }
+
+    pub fn ssh() -> Self {
+        Self {
+            policy: RetryConfig::current().ssh,
+            is_transient: ssh_transient,
+        }
+    }
+}
+
+/// Runs `op` until it succeeds, fails with an error `is_transient` rejects,
+/// or the policy runs out of attempts. `what` names the operation in
+/// warnings and the log.
+pub fn retry<T, E: Display>(
+    policy: &RetryPolicy,
+    what: &str,
+    mut op: impl FnMut() -> Result<T, E>,
+    is_transient: impl Fn(&E) -> bool,
+) -> Result<T, E> {
+    let attempts = policy.attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Err(e) if attempt < attempts && is_transient(&e) => {
+                let delay = policy.delay(attempt);
+                print_warn!(
+                    "⏳ {} failed ({}); retrying in {:.1}s (attempt {}/{})",
+                    what,
+                    e,
+                    delay.as_secs_f64(),
+                    attempt + 1,
+                    attempts
+                );
+                log_warn!("retry", what, &e.to_string());
+                thread::sleep(delay);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Downloads `url`, retrying connection failures and 5xx responses.
+pub fn download(url: &str) -> std::io::Result<Vec<u8>> {
+    let policy = RetryConfig::current().download;
+    retry(
+        &policy,
+        url,
+        || {
+            reqwest::blocking::get(url)
+                .and_then(|r| r.error_for_status())
+                .and_then(|r| r.bytes())
+                .map(|b| b.to_vec())
+        },
+        http_transient,
+    )
+    .map_err(|e| std::io::Error::other(format!("failed to download {url}: {e}")))
+}
@@ -1,22 +1,52 @@
 // Module: Shell/Command
 // Location: cli/src/shell/command.rs
-use crate::{print_error, print_info, print_warn};
+use crate::{
+    config::CONFIG,
+    core::retry::{self, Retry},
+    core::shell::{
+        output::{self, Spinner},
+        privilege::Escalation,
+        quote,
+    },
+    log_info, log_warn, print_error, print_info, print_warn,
+};
 use colored::Colorize;
-use std::process::{Command, Output, Stdio};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitStatus, Output, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
 
 #[derive(Debug)]
 pub struct ShellCommand {
     pub command: String,
     pub args: Vec<String>,
     pub requires_sudo: bool,
+    pub env: Vec<(String, String)>,
+    pub cwd: Option<PathBuf>,
+    pub timeout: Option<Duration>,
+    /// Prefix for streamed output lines, defaults to the program name
+    pub label: Option<String>,
+    /// Retries the command when it fails in a way `Retry` deems transient
+    pub retry: Option<Retry>,
 }
 
+/// Lines of output kept in the log for each command.
+const LOG_TAIL_LINES: usize = 20;
+
 impl ShellCommand {
     pub fn new(command: &str) -> Self {
         Self {
             command: command.to_owned(),
             args: vec![],
             requires_sudo: false,
+            env: vec![],
+            cwd: None,
+            timeout: None,
+            label: None,
+            retry: None,
         }
     }
 
@@ -34,37 +64,106 @@ impl ShellCommand {
         self
     }
 
+    pub fn with_env<I, K, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.env
+            .extend(vars.into_iter().map(|(k, v)| (k.into(), v.into())));
+        self
+    }
+
+    pub fn with_cwd(mut self, cwd: Option<PathBuf>) -> Self {
+        self.cwd = cwd;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_retry(mut self, retry: Retry) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    fn label(&self) -> String {
+        self.label.clone().unwrap_or_else(|| {
+            Path::new(&self.command)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| self.command.clone())
+        })
+    }
+
+    /// The command's own timeout, or `command_timeout` from the config.
+    fn effective_timeout(&self) -> Option<Duration> {
+        self.timeout.or_else(|| {
+            CONFIG
+                .get()
+                .and_then(|cfg| cfg.command_timeout)
+                .map(Duration::from_secs)
+        })
+    }
+
     pub fn as_string(&self) -> String {
         let mut parts = Vec::new();
-        if self.requires_sudo && !cfg!(windows) {
-            parts.push("sudo".to_owned());
+        if self.requires_sudo
+            && !cfg!(windows)
+            && let Some(program) = Escalation::current().program()
+        {
+            parts.push(program.to_owned());
         }
         parts.push(self.command.clone());
         parts.extend(self.args.clone());
-        parts.join(" ")
+        quote::join(parts)
     }
 
     pub fn dry_run(&self) {
-        println!("💡 [Dry Run] {}", self.as_string().cyan());
+        let cwd = self
+            .cwd
+            .as_ref()
+            .map(|dir| format!(" (in {})", dir.display()))
+            .unwrap_or_default();
+        println!("💡 [Dry Run] {}{}", self.as_string().cyan(), cwd);
     }
 
     fn build_command(&self) -> Command {
-        if cfg!(windows) {
-            let full_cmd = format!("{} {}", self.command, self.args.join(" "));
-            let mut cmd = Command::new("cmd");
-            cmd.args(["/C", &full_cmd]);
+        let mut cmd = if cfg!(windows) && !is_windows_binary(&self.command) {
+            // .cmd, .bat and .ps1 shims (scoop, npm, ...) need a shell
+            let mut line = vec![self.command.clone()];
+            line.extend(self.args.iter().cloned());
+            let mut cmd = Command::new("powershell");
+            cmd.args([
+                "-NoProfile",
+                "-Command",
+                &format!("& {}; exit $LASTEXITCODE", quote::join_powershell(line)),
+            ]);
             cmd
         } else {
-            let mut cmd = if self.requires_sudo {
-                let mut c = Command::new("sudo");
-                c.arg(&self.command);
-                c
+            let (program, args) = if self.requires_sudo && !cfg!(windows) {
+                Escalation::current().wrap(&self.command, &self.args, &self.env)
             } else {
-                Command::new(&self.command)
+                (self.command.clone(), self.args.clone())
             };
-            cmd.args(&self.args);
+            let mut cmd = Command::new(program);
+            cmd.args(args);
             cmd
+        };
+
+        cmd.envs(self.env.iter().map(|(k, v)| (k, v)));
+        if let Some(ref dir) = self.cwd {
+            cmd.current_dir(dir);
         }
+        cmd
     }
 
     pub fn execute(&self) -> std::io::Result<Output> {
@@ -80,53 +179,171 @@ impl ShellCommand {
         }
     }
 
-    pub fn run_verbose(&self, dry_run: bool) {
-        println!("🚀 Running: {}", self.as_string().cyan());
+    /// Runs the command, streaming its output line by line as it arrives.
+    ///
+    /// Lines are prefixed with the command's label and the tail of the
+    /// output is logged. In quiet mode only a spinner is shown, and the
+    /// output is printed if the command fails. Transient failures are
+    /// retried when the command has a [`Retry`].
+    pub fn run_verbose(&self, dry_run: bool) -> io::Result<()> {
+        if dry_run {
+            self.dry_run();
+            return Ok(());
+        }
+
+        let Some(ref policy) = self.retry else {
+            return self.run_streamed().map_err(|f| f.error);
+        };
+        retry::retry(
+            &policy.policy,
+            &self.as_string(),
+            || self.run_streamed(),
+            |f| f.transient,
+        )
+        .map_err(|f| f.error)
+    }
+
+    fn run_streamed(&self) -> Result<(), Failure> {
+        let quiet = output::is_quiet();
+        if !quiet {
+            println!("🚀 Running: {}", self.as_string().cyan());
+        }
 
-        if let Some(result) = self.execute_with_dry_run(dry_run) {
-            match result {
-                Ok(output) => {
-                    let stdout = String::from_utf8_lossy(&output.stdout);
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-
-                    if output.status.success() {
-                        if !stdout.trim().is_empty() {
-                            println!("{}", stdout.green());
-                        }
-                        if !stderr.trim().is_empty() {
-                            print_error!("{}", stderr.yellow());
-                        }
-                    } else {
-                        print_error!("{}", stderr.red());
-                    }
+        let start = Instant::now();
+        let mut child = self
+            .build_command()
+            .stdin(Stdio::inherit())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                print_error!("❌ Failed to execute: {}", e);
+                Failure {
+                    error: e,
+                    transient: false,
                 }
-                Err(e) => {
-                    print_error!("❌ Failed to execute: {}", e);
+            })?;
+
+        let label = self.label();
+        let spinner = quiet.then(|| Spinner::start(self.as_string()));
+        let (status, captured) =
+            output::stream_child(&mut child, &label, self.effective_timeout(), !quiet);
+        drop(spinner);
+
+        let elapsed = start.elapsed().as_secs_f64();
+        let command = self.as_string();
+        let code = status.as_ref().ok().and_then(|s| s.code());
+        let result = status.and_then(|status| {
+            if status.success() {
+                Ok(())
+            } else {
+                Err(io::Error::other(format!(
+                    "`{}` exited with status code {}",
+                    command,
+                    code.unwrap_or(-1)
+                )))
+            }
+        });
+
+        match result {
+            Ok(()) => {
+                log_info!("command", &command, &captured.tail(LOG_TAIL_LINES), elapsed);
+                Ok(())
+            }
+            Err(e) => {
+                if quiet {
+                    println!("🚀 Ran: {}", command.cyan());
+                    captured.dump(&label);
                 }
+                print_error!("❌ {}", e);
+                log_warn!(
+                    "command",
+                    &command,
+                    &format!("{e}\n{}", captured.tail(LOG_TAIL_LINES)),
+                    elapsed
+                );
+                let transient = self
+                    .retry
+                    .as_ref()
+                    .is_some_and(|r| (r.is_transient)(code, &captured.tail(usize::MAX)));
+                Err(Failure {
+                    error: e,
+                    transient,
+                })
             }
         }
     }
 
-    // pub fn run(&self, dry_run: bool) -> Option<std::io::Result<()>> {
-    //     if dry_run {
-    //         self.dry_run();
-    //         return Some(Ok(()));
-    //     }
+    /// Runs the command with its output streamed under its label, returning
+    /// the exit status and output instead of failing on a non-zero status.
+    ///
+    /// Meant for running several commands side by side: stdin is closed,
+    /// nothing is retried, and output is only echoed outside quiet mode.
+    pub fn run_with_status(&self) -> io::Result<(ExitStatus, output::CapturedOutput)> {
+        self.stream_with_status(!output::is_quiet())
+    }
 
-    //     match self.execute() {
-    //         Ok(output) => {
-    //             if output.status.success() {
-    //                 Some(Ok(()))
-    //             } else {
-    //                 Some(Err(std::io::Error::new(
-    //                     std::io::ErrorKind::Other,
-    //                     "Command failed",
-    //                 )))
-    //             }
-    //         }
-    //         Err(e) => Some(Err(e)),
-    //     }
-    // }
+    /// Like [`ShellCommand::run_with_status`], but never echoes the output.
+    pub fn capture(&self) -> io::Result<(ExitStatus, output::CapturedOutput)> {
+        self.stream_with_status(false)
+    }
+
+    fn stream_with_status(&self, echo: bool) -> io::Result<(ExitStatus, output::CapturedOutput)> {
+        let mut child = self
+            .build_command()
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let (status, captured) =
+            output::stream_child(&mut child, &self.label(), self.effective_timeout(), echo);
+        status.map(|status| (status, captured))
+    }
+
+    /// Runs the command with inherited stdio, honouring the timeout.
+    ///
+    /// A non-zero exit status is reported as an error.
+    pub fn run(&self, dry_run: bool) -> io::Result<()> {
+        if dry_run {
+            self.dry_run();
+            return Ok(());
+        }
+        if output::is_quiet() {
+            return self.run_verbose(false);
+        }
+
+        println!("🚀 Running: {}", self.as_string().cyan());
+        let mut child = self
+            .build_command()
+            .stdin(Stdio::inherit())
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+
+        let status = wait_with_timeout(&mut child, self.effective_timeout())?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!(
+                "`{}` exited with status code {}",
+                self.as_string(),
+                status.code().unwrap_or(-1)
+            )))
+        }
+    }
+
+    /// Runs the command without any output and reports whether it exited 0.
+    pub fn succeeds(&self) -> bool {
+        self.build_command()
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .and_then(|mut child| wait_with_timeout(&mut child, self.timeout))
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
 
     pub fn run_interactive(&self, dry_run: bool) -> std::io::Result<()> {
         if dry_run {
@@ -161,20 +378,118 @@ impl ShellCommand {
 
     pub fn from_script(script: &str, sudo: bool) -> Self {
         if cfg!(windows) {
-            Self::new("powershell")
-                .with_args(["-Command", script])
-                .with_sudo(sudo)
+            Self::from_script_with_shell(script, "powershell", sudo)
         } else {
-            Self::new("sh").with_args(["-c", script]).with_sudo(sudo)
+            Self::from_script_with_shell(script, "sh", sudo)
+        }
+    }
+
+    /// Runs `script` through the given shell, using the flag that shell
+    /// expects for an inline command.
+    pub fn from_script_with_shell(script: &str, shell: &str, sudo: bool) -> Self {
+        let name = Path::new(shell)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or(shell)
+            .to_lowercase();
+        let flag = match name.as_str() {
+            "powershell" | "pwsh" => "-Command",
+            "cmd" => "/C",
+            _ => "-c",
+        };
+        Self::new(shell).with_args([flag, script]).with_sudo(sudo)
+    }
+
+    /// Runs `script` on `remote` over ssh with `ssh_args`, escalating there
+    /// when `sudo` is set. ssh hands its command to the remote login shell
+    /// as one string, so the script is quoted to reach `sh -c` unchanged.
+    pub fn from_remote_script(remote: &str, ssh_args: &[String], script: &str, sudo: bool) -> Self {
+        let mut remote_cmd = Vec::new();
+        if sudo {
+            remote_cmd.push("sudo");
         }
+        remote_cmd.extend(["sh", "-c", script]);
+        let mut args = ssh_args.to_vec();
+        args.extend([
+            "--".to_string(),
+            remote.to_string(),
+            quote::join_posix(remote_cmd),
+        ]);
+        Self::new("ssh").with_args(args).with_retry(Retry::ssh())
     }
+}
 
-    pub fn from_remote_script(remote: &str, script: &str, sudo: bool) -> Self {
-        let wrapped = format!("ssh {} '{}'", remote, script);
-        Self::from_script(&wrapped, sudo)
+/// A failed run and whether its [`Retry`] considers it transient.
+struct Failure {
+    error: io::Error,
+    transient: bool,
+}
+
+impl std::fmt::Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
     }
 }
 
+/// Whether Windows can start `program` directly, i.e. it names or resolves
+/// on PATH to an `.exe` or `.com` rather than a script shim.
+fn is_windows_binary(program: &str) -> bool {
+    let is_binary = |path: &Path| {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("exe") || ext.eq_ignore_ascii_case("com"))
+    };
+
+    let path = Path::new(program);
+    if path.extension().is_some() {
+        return is_binary(path);
+    }
+    let Some(dirs) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&dirs).any(|dir| {
+        ["exe", "com"]
+            .iter()
+            .any(|ext| dir.join(program).with_extension(ext).is_file())
+    })
+}
+
+/// Waits for `child`, killing it once `timeout` has elapsed.
+fn wait_with_timeout(child: &mut Child, timeout: Option<Duration>) -> io::Result<ExitStatus> {
+    match timeout {
+        Some(timeout) => wait_until(child, Instant::now() + timeout, timeout),
+        None => child.wait(),
+    }
+}
+
+/// Waits for `child`, killing it at `deadline`. `timeout` is the full
+/// budget, reported in the error.
+pub(crate) fn wait_until(
+    child: &mut Child,
+    deadline: Instant,
+    timeout: Duration,
+) -> io::Result<ExitStatus> {
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if Instant::now() >= deadline {
+            return Err(kill_timed_out(child, timeout));
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+}
+
+/// Kills `child` and builds the error for running past `timeout`.
+pub(crate) fn kill_timed_out(child: &mut Child, timeout: Duration) -> io::Error {
+    let _ = child.kill();
+    let _ = child.wait();
+    io::Error::new(
+        io::ErrorKind::TimedOut,
+        format!("timed out after {}s", timeout.as_secs()),
+    )
+}
+
 pub fn command_exists(cmd: &str) -> bool {
     Command::new(if cfg!(windows) { "where" } else { "which" })
         .arg(cmd)
@@ -185,6 +500,18 @@ pub fn command_exists(cmd: &str) -> bool {
         .unwrap_or(false)
 }
 
+/// Whether tranquility is running as root (effective uid 0).
+pub fn is_root() -> bool {
+    if cfg!(windows) {
+        return false;
+    }
+    Command::new("id")
+        .arg("-u")
+        .output()
+        .map(|out| String::from_utf8_lossy(&out.stdout).trim() == "0")
+        .unwrap_or(false)
+}
+
 pub fn check_command(cmd: &str, friendly_name: &str) -> bool {
     let ok = command_exists(cmd);
 
@@ -224,9 +551,9 @@ pub fn run_shell_command(command: &str) {
     }
 }
 
-pub fn execute_package_cmd(cmd: &str, args: &[&str], sudo: bool, dry_run: bool) {
+pub fn execute_package_cmd(cmd: &str, args: &[&str], sudo: bool, dry_run: bool) -> io::Result<()> {
     ShellCommand::new(cmd)
         .with_args(args.iter().copied())
         .with_sudo(sudo)
-        .run_verbose(dry_run);
+        .run_verbose(dry_run)
 }
@@ -1,6 +1,9 @@
 // Module: Shell
 // Location: cli/src/shell/mod.rs
 pub mod command;
+pub mod output;
+pub mod privilege;
+pub mod quote;
 pub mod runner;
 pub mod script_runner;
 
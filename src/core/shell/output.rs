@@ -0,0 +1,212 @@
+// Module: Shell/Output
+// Location: cli/src/shell/output.rs
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    process::{Child, ExitStatus},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use colored::Colorize;
+
+use crate::core::shell::command::{kill_timed_out, wait_until};
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Hides command output behind a spinner, printing it only on failure.
+pub fn set_quiet(enabled: bool) {
+    QUIET.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// Everything a command printed, in the order it arrived.
+#[derive(Debug, Default)]
+pub struct CapturedOutput {
+    pub lines: Vec<(Stream, String)>,
+}
+
+impl CapturedOutput {
+    /// The last `n` lines, joined with newlines.
+    pub fn tail(&self, n: usize) -> String {
+        let skip = self.lines.len().saturating_sub(n);
+        self.lines[skip..]
+            .iter()
+            .map(|(_, line)| line.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The lines from `stream` alone, joined with newlines.
+    pub fn text(&self, stream: Stream) -> String {
+        self.lines
+            .iter()
+            .filter(|(s, _)| *s == stream)
+            .map(|(_, line)| line.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Prints every captured line with its prefix.
+    pub fn dump(&self, label: &str) {
+        for (stream, line) in &self.lines {
+            print_line(label, *stream, line);
+        }
+    }
+}
+
+/// `[label] line`, with stderr lines highlighted.
+pub fn format_line(label: &str, stream: Stream, line: &str) -> String {
+    let prefix = format!("[{label}]");
+    match stream {
+        Stream::Stdout => format!("{} {}", prefix.cyan(), line),
+        Stream::Stderr => format!("{} {}", prefix.yellow(), line.yellow()),
+    }
+}
+
+fn print_line(label: &str, stream: Stream, line: &str) {
+    let line = format_line(label, stream, line);
+    match stream {
+        Stream::Stdout => println!("{line}"),
+        Stream::Stderr => eprintln!("{line}"),
+    }
+}
+
+/// Reads the child's piped stdout and stderr line by line until both close,
+/// echoing each line as it arrives unless `echo` is false.
+///
+/// The child is killed once `timeout` elapses, whether or not it keeps
+/// printing or has closed its pipes. The output captured so far is
+/// returned either way, so callers can show it on failure.
+pub fn stream_child(
+    child: &mut Child,
+    label: &str,
+    timeout: Option<Duration>,
+    echo: bool,
+) -> (io::Result<ExitStatus>, CapturedOutput) {
+    let (tx, rx) = mpsc::channel();
+    let mut readers = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        readers.push(forward_lines(stdout, Stream::Stdout, tx.clone()));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        readers.push(forward_lines(stderr, Stream::Stderr, tx.clone()));
+    }
+    drop(tx);
+
+    let deadline = timeout.map(|t| Instant::now() + t);
+    let mut output = CapturedOutput::default();
+
+    let poll = Duration::from_millis(100);
+
+    loop {
+        let wait = match (deadline, timeout) {
+            (Some(deadline), Some(timeout)) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    return (Err(kill_timed_out(child, timeout)), output);
+                }
+                poll.min(deadline - now)
+            }
+            _ => poll,
+        };
+
+        match rx.recv_timeout(wait) {
+            Ok((stream, line)) => {
+                if echo {
+                    print_line(label, stream, &line);
+                }
+                output.lines.push((stream, line));
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    for reader in readers {
+        let _ = reader.join();
+    }
+    // The pipes may close well before the child exits
+    let status = match (deadline, timeout) {
+        (Some(deadline), Some(timeout)) => wait_until(child, deadline, timeout),
+        _ => child.wait(),
+    };
+    (status, output)
+}
+
+fn forward_lines<R: Read + Send + 'static>(
+    source: R,
+    stream: Stream,
+    tx: mpsc::Sender<(Stream, String)>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(source);
+        let mut buf = Vec::new();
+        // Lossy per line, so one bad byte doesn't end the stream
+        while reader.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
+            let line = String::from_utf8_lossy(&buf)
+                .trim_end_matches(['\r', '\n'])
+                .to_string();
+            if tx.send((stream, line)).is_err() {
+                break;
+            }
+            buf.clear();
+        }
+    })
+}
+
+/// A one-line spinner on stderr, cleared when dropped.
+pub struct Spinner {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Spinner {
+    pub fn start(message: String) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&stop);
+        let handle = thread::spawn(move || {
+            let frames = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+            let mut i = 0;
+            while !flag.load(Ordering::Relaxed) {
+                eprint!(
+                    "\r{} {}",
+                    frames[i % frames.len()].to_string().cyan(),
+                    message
+                );
+                let _ = io::stderr().flush();
+                i += 1;
+                thread::sleep(Duration::from_millis(100));
+            }
+            eprint!("\r\x1b[2K");
+            let _ = io::stderr().flush();
+        });
+
+        Self {
+            stop,
+            handle: Some(handle),
+        }
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
@@ -0,0 +1,205 @@
+// Module: Shell/Privilege
+// Location: cli/src/shell/privilege.rs
+use std::{
+    process::{Command, Stdio},
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use crate::{
+    config::CONFIG,
+    core::shell::command::{command_exists, is_root},
+    print_warn,
+};
+
+/// How commands that need root are elevated.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Display,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Escalation {
+    /// No escalation when already root, otherwise the first of sudo, doas,
+    /// run0 and pkexec that is installed
+    #[default]
+    Auto,
+    Sudo,
+    Doas,
+    Run0,
+    Pkexec,
+    /// Run privileged commands as the current user
+    None,
+}
+
+const DETECT_ORDER: [Escalation; 4] = [
+    Escalation::Sudo,
+    Escalation::Doas,
+    Escalation::Run0,
+    Escalation::Pkexec,
+];
+
+/// How often a sudo timestamp is refreshed while a run is in progress.
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(60);
+
+impl Escalation {
+    pub fn program(&self) -> Option<&'static str> {
+        match self {
+            Escalation::Sudo => Some("sudo"),
+            Escalation::Doas => Some("doas"),
+            Escalation::Run0 => Some("run0"),
+            Escalation::Pkexec => Some("pkexec"),
+            Escalation::Auto | Escalation::None => None,
+        }
+    }
+
+    /// Picks the concrete strategy. Root never escalates.
+    pub fn resolve(self, is_root: bool, installed: impl Fn(&str) -> bool) -> Escalation {
+        if is_root {
+            return Escalation::None;
+        }
+        match self {
+            Escalation::Auto => DETECT_ORDER
+                .into_iter()
+                .find(|e| e.program().is_some_and(&installed))
+                .unwrap_or(Escalation::None),
+            other => other,
+        }
+    }
+
+    /// The strategy for this process, from the config and the current user.
+    ///
+    /// Warns once when `auto` finds no escalation tool, since privileged
+    /// commands then run as the current user and fail on their own terms.
+    pub fn current() -> Escalation {
+        static CURRENT: OnceLock<Escalation> = OnceLock::new();
+        *CURRENT.get_or_init(|| {
+            let configured = CONFIG.get().map(|cfg| cfg.escalation).unwrap_or_default();
+            let root = is_root();
+            let resolved = configured.resolve(root, command_exists);
+            let missing = configured == Escalation::Auto && resolved == Escalation::None;
+            if missing && !root && !cfg!(windows) {
+                print_warn!(
+                    "⚠️ None of sudo, doas, run0 or pkexec is installed; commands needing root will run as the current user"
+                );
+            }
+            resolved
+        })
+    }
+
+    /// Program and arguments that run `program args` elevated with `env`.
+    ///
+    /// sudo is told which variables to keep; the other tools reset the
+    /// environment, so the variables are passed through `env`.
+    pub fn wrap(
+        &self,
+        program: &str,
+        args: &[String],
+        env: &[(String, String)],
+    ) -> (String, Vec<String>) {
+        let Some(escalate) = self.program() else {
+            return (program.to_string(), args.to_vec());
+        };
+
+        let mut wrapped = Vec::new();
+        if *self == Escalation::Sudo {
+            if !env.is_empty() {
+                let names: Vec<&str> = env.iter().map(|(k, _)| k.as_str()).collect();
+                wrapped.push(format!("--preserve-env={}", names.join(",")));
+            }
+        } else if !env.is_empty() {
+            wrapped.push("env".to_string());
+            wrapped.extend(env.iter().map(|(k, v)| format!("{k}={v}")));
+        }
+        wrapped.push(program.to_string());
+        wrapped.extend(args.iter().cloned());
+
+        (escalate.to_string(), wrapped)
+    }
+
+    /// Prompts for credentials once so later commands don't.
+    ///
+    /// Only sudo and doas (with `persist`) cache credentials; run0 and
+    /// pkexec authenticate every command.
+    pub fn authenticate(&self) -> bool {
+        let args: &[&str] = match self {
+            Escalation::Sudo => &["-v"],
+            Escalation::Doas => &["true"],
+            _ => return true,
+        };
+        Command::new(self.program().unwrap_or_default())
+            .args(args)
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
+}
+
+/// Keeps cached sudo credentials fresh until dropped.
+pub struct CredentialKeepalive {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for CredentialKeepalive {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Authenticates once for a run that will execute privileged commands and,
+/// for sudo, refreshes the timestamp in the background.
+///
+/// Returns `None` when no escalation is needed or authentication failed.
+pub fn authenticate_for_run(dry_run: bool) -> Option<CredentialKeepalive> {
+    let escalation = Escalation::current();
+    if dry_run || escalation == Escalation::None {
+        return None;
+    }
+
+    if !escalation.authenticate() {
+        print_warn!(
+            "⚠️ {} authentication failed; you may be prompted again",
+            escalation
+        );
+        return None;
+    }
+    if escalation != Escalation::Sudo {
+        return None;
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&stop);
+    let handle = thread::spawn(move || {
+        let tick = Duration::from_millis(500);
+        let mut waited = Duration::ZERO;
+        while !flag.load(Ordering::Relaxed) {
+            thread::sleep(tick);
+            waited += tick;
+            if waited >= KEEPALIVE_INTERVAL {
+                waited = Duration::ZERO;
+                // -n never prompts; a failure just means the next command asks
+                let _ = Command::new("sudo")
+                    .args(["-n", "-v"])
+                    .stdin(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status();
+            }
+        }
+    });
+
+    Some(CredentialKeepalive {
+        stop,
+        handle: Some(handle),
+    })
+}
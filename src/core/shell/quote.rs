@@ -0,0 +1,183 @@
+// Module: Shell/Quote
+// Location: cli/src/shell/quote.rs
+use std::borrow::Cow;
+
+/// Characters PowerShell treats as single quotes inside a quoted string.
+const POWERSHELL_QUOTES: [char; 5] = ['\'', '\u{2018}', '\u{2019}', '\u{201A}', '\u{201B}'];
+
+fn is_posix_safe(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "_@%+=:,./-".contains(c)
+}
+
+fn is_powershell_safe(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "_./:\\-".contains(c)
+}
+
+/// Quotes `arg` for a POSIX shell, leaving plain words untouched.
+pub fn quote_posix(arg: &str) -> Cow<'_, str> {
+    if !arg.is_empty() && arg.chars().all(is_posix_safe) {
+        return Cow::Borrowed(arg);
+    }
+    Cow::Owned(format!("'{}'", arg.replace('\'', r"'\''")))
+}
+
+/// Quotes `arg` for PowerShell, leaving plain words untouched.
+pub fn quote_powershell(arg: &str) -> Cow<'_, str> {
+    if !arg.is_empty() && arg.chars().all(is_powershell_safe) {
+        return Cow::Borrowed(arg);
+    }
+    let mut quoted = String::with_capacity(arg.len() + 2);
+    quoted.push('\'');
+    for c in arg.chars() {
+        if POWERSHELL_QUOTES.contains(&c) {
+            quoted.push(c);
+        }
+        quoted.push(c);
+    }
+    quoted.push('\'');
+    Cow::Owned(quoted)
+}
+
+pub fn join_posix<I, S>(args: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    args.into_iter()
+        .map(|arg| quote_posix(arg.as_ref()).into_owned())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn join_powershell<I, S>(args: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    args.into_iter()
+        .map(|arg| quote_powershell(arg.as_ref()).into_owned())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Joins `args` with the quoting of this platform's shell.
+pub fn join<I, S>(args: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    if cfg!(windows) {
+        join_powershell(args)
+    } else {
+        join_posix(args)
+    }
+}
+
+/// Splits a POSIX shell command line into words, handling single quotes,
+/// double quotes and backslash escapes but no expansions.
+///
+/// Returns `None` for an unterminated quote or trailing backslash.
+pub fn split_posix(line: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' | '\t' | '\n' => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            c @ ('$' | '`' | '"' | '\\') => word.push(c),
+                            '\n' => {}
+                            c => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                match chars.next()? {
+                    '\n' => {}
+                    c => word.push(c),
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+
+    if in_word {
+        words.push(word);
+    }
+    Some(words)
+}
+
+/// Splits a PowerShell argument list made of bare words and single-quoted
+/// strings, the forms [`quote_powershell`] produces.
+///
+/// Returns `None` for an unterminated quote.
+pub fn split_powershell(line: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' | '\t' => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            q if POWERSHELL_QUOTES.contains(&q) => {
+                in_word = true;
+                loop {
+                    let c = chars.next()?;
+                    if !POWERSHELL_QUOTES.contains(&c) {
+                        word.push(c);
+                    } else if chars.peek().is_some_and(|n| POWERSHELL_QUOTES.contains(n)) {
+                        // A doubled quote is a literal one
+                        word.push(chars.next()?);
+                    } else {
+                        break;
+                    }
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+
+    if in_word {
+        words.push(word);
+    }
+    Some(words)
+}
@@ -1,39 +1,174 @@
 use crate::{
-    models::application::{Application, InstallMethod},
-    print_info, print_success,
+    core::template::TemplateContext,
+    log_error, log_info, log_warn,
+    models::application::{Application, ApplicationVersion, InstallMethod},
+    print_error, print_info, print_success, print_warn,
 };
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 pub struct InstallRunner<'a> {
     pub app: &'a Application,
+    pub version: &'a ApplicationVersion,
     pub method: &'a InstallMethod,
+    pub context: TemplateContext,
     pub dry_run: bool,
 }
 
+/// Result of a single install or uninstall run.
+#[derive(Debug)]
+pub struct InstallOutcome {
+    pub duration: Duration,
+    pub error: Option<String>,
+    /// Problem that didn't fail the run, such as a hold that couldn't be set
+    pub warning: Option<String>,
+}
+
+impl InstallOutcome {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
 impl<'a> InstallRunner<'a> {
-    pub fn new(app: &'a Application, method: &'a InstallMethod, dry_run: bool) -> Self {
+    pub fn new(
+        app: &'a Application,
+        version: &'a ApplicationVersion,
+        method: &'a InstallMethod,
+        context: TemplateContext,
+        dry_run: bool,
+    ) -> Self {
         Self {
             app,
+            version,
             method,
+            context,
             dry_run,
         }
     }
 
-    pub fn run_install(&self) -> std::time::Duration {
+    /// Installs the app and, outside dry-run mode, re-runs its check command
+    /// to confirm the install actually took effect. A pinned package that
+    /// can't be held only yields a warning.
+    pub fn run_install(&self) -> InstallOutcome {
         print_info!("🚀 Installing {}...", self.app.name);
         let start = Instant::now();
-        self.method.install(self.dry_run);
+        let mut warning = None;
+        let result = self
+            .method
+            .install(&self.context, self.dry_run)
+            .map_err(|e| e.to_string())
+            .inspect(|()| {
+                if let Err(e) = self.method.hold(&self.context, self.dry_run) {
+                    let msg = format!("failed to hold the package version: {e}");
+                    print_warn!("⚠️ {}: {}", self.app.name, msg);
+                    log_warn!("install", &self.app.name, &msg);
+                    warning = Some(msg);
+                }
+            })
+            .and_then(|()| {
+                if self.dry_run {
+                    Ok(())
+                } else {
+                    self.version
+                        .verify()
+                        .map_err(|e| format!("verification failed: {e}"))
+                }
+            });
         let duration = start.elapsed();
-        print_success!("✅ Installed {} in {:.2?}", self.app.name, duration);
-        duration
+
+        match result {
+            Ok(()) => {
+                print_success!("✅ Installed {} in {:.2?}", self.app.name, duration);
+                log_info!("install", &self.app.name, "success", duration.as_secs_f64());
+                InstallOutcome {
+                    duration,
+                    error: None,
+                    warning,
+                }
+            }
+            Err(e) => {
+                print_error!("❌ Failed to install {}: {}", self.app.name, e);
+                log_error!("install", &self.app.name, &e, duration.as_secs_f64());
+                InstallOutcome {
+                    duration,
+                    error: Some(e),
+                    warning,
+                }
+            }
+        }
     }
 
-    pub fn run_uninstall(&self) -> std::time::Duration {
+    pub fn run_upgrade(&self) -> InstallOutcome {
+        print_info!("⬆️ Upgrading {}...", self.app.name);
+        let start = Instant::now();
+        let result = self.method.upgrade(&self.context, self.dry_run);
+        let duration = start.elapsed();
+
+        match result {
+            Ok(()) => {
+                print_success!("✅ Upgraded {} in {:.2?}", self.app.name, duration);
+                log_info!("upgrade", &self.app.name, "success", duration.as_secs_f64());
+                InstallOutcome {
+                    duration,
+                    error: None,
+                    warning: None,
+                }
+            }
+            Err(e) => {
+                print_error!("❌ Failed to upgrade {}: {}", self.app.name, e);
+                log_error!(
+                    "upgrade",
+                    &self.app.name,
+                    &e.to_string(),
+                    duration.as_secs_f64()
+                );
+                InstallOutcome {
+                    duration,
+                    error: Some(e.to_string()),
+                    warning: None,
+                }
+            }
+        }
+    }
+
+    /// Removes the app and, unless `keep_repo` is set, its vendor repository.
+    pub fn run_uninstall(&self, keep_repo: bool) -> InstallOutcome {
         print_info!("🧹 Uninstalling {}...", self.app.name);
         let start = Instant::now();
-        self.method.uninstall(self.dry_run);
+        let result = self
+            .method
+            .uninstall(&self.context, keep_repo, self.dry_run);
         let duration = start.elapsed();
-        print_success!("🗑️ Uninstalled {} in {:.2?}", self.app.name, duration);
-        duration
+
+        match result {
+            Ok(()) => {
+                print_success!("🗑️ Uninstalled {} in {:.2?}", self.app.name, duration);
+                log_info!(
+                    "uninstall",
+                    &self.app.name,
+                    "success",
+                    duration.as_secs_f64()
+                );
+                InstallOutcome {
+                    duration,
+                    error: None,
+                    warning: None,
+                }
+            }
+            Err(e) => {
+                print_error!("❌ Failed to uninstall {}: {}", self.app.name, e);
+                log_error!(
+                    "uninstall",
+                    &self.app.name,
+                    &e.to_string(),
+                    duration.as_secs_f64()
+                );
+                InstallOutcome {
+                    duration,
+                    error: Some(e.to_string()),
+                    warning: None,
+                }
+            }
+        }
     }
 }
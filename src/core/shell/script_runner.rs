@@ -13,6 +13,8 @@ pub struct ShellScriptRunner {
     pub script: String,
     pub source: ScriptSource,
     pub remote: Option<String>,
+    /// Options passed to ssh before the remote, e.g. key, port and jump host
+    pub ssh_args: Vec<String>,
     pub use_sudo: bool,
     pub dry_run: bool,
 }
@@ -44,7 +46,7 @@ impl ShellScriptRunner {
     //     self
     // }
 
-    pub fn run_verbose(&self) {
+    pub fn run_verbose(&self) -> std::io::Result<()> {
         let label = match &self.source {
             ScriptSource::Inline(_) => "[inline]",
             ScriptSource::File(path) => path,
@@ -63,7 +65,7 @@ impl ShellScriptRunner {
             remote_label
         );
 
-        self.to_command().run_verbose(self.dry_run);
+        self.to_command().run_verbose(self.dry_run)
     }
 
     // pub fn run_silent(&self) -> Option<std::io::Result<()>> {
@@ -76,7 +78,12 @@ impl ShellScriptRunner {
 
     fn to_command(&self) -> ShellCommand {
         match &self.remote {
-            Some(remote) => ShellCommand::from_remote_script(remote, &self.script, self.use_sudo),
+            Some(remote) => ShellCommand::from_remote_script(
+                remote,
+                &self.ssh_args,
+                &self.script,
+                self.use_sudo,
+            ),
             None => ShellCommand::from_script(&self.script, self.use_sudo),
         }
     }
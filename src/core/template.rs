@@ -0,0 +1,174 @@
+// Module: Core/Template
+// Location: cli/src/core/template.rs
+use std::collections::BTreeMap;
+
+use heck::ToKebabCase;
+use os_info::Type as OSType;
+use thiserror::Error;
+
+use crate::{
+    config::{CONFIG, TranquilityConfig},
+    models::{application::ApplicationVersion, system::SystemInfo, vps::facts::RemoteFacts},
+};
+
+/// Variables that are always available to install steps and package names.
+pub const BUILTIN_VARIABLES: [&str; 6] = ["arch", "os", "distro", "home", "version", "config_dir"];
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TemplateError {
+    #[error("unknown template variable `{{{{{0}}}}}`")]
+    UnknownVariable(String),
+}
+
+/// Values used to expand `{{name}}` placeholders.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateContext {
+    vars: BTreeMap<String, String>,
+    /// Set for a VPS, whose paths only its own shell can resolve
+    remote: bool,
+}
+
+impl TemplateContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_var(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    pub fn set(&mut self, name: &str, value: impl Into<String>) {
+        self.vars.insert(name.to_owned(), value.into());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(String::as_str)
+    }
+
+    /// Whether steps rendered with this context run on a VPS.
+    pub fn is_remote(&self) -> bool {
+        self.remote
+    }
+
+    /// Builds the context for installing `version` on the local system.
+    ///
+    /// User-defined variables from the config are added first so the
+    /// built-in names always win.
+    pub fn for_version(system: &SystemInfo, version: &ApplicationVersion) -> Self {
+        let mut ctx = Self::new();
+        for (name, value) in user_variables() {
+            ctx.set(&name, value);
+        }
+
+        let home = dirs::home_dir()
+            .map(|p| p.display().to_string())
+            .unwrap_or_default();
+        let config_dir = TranquilityConfig::config_dir()
+            .map(|p| p.display().to_string())
+            .unwrap_or_default();
+
+        ctx.with_var("arch", system.arch())
+            .with_var("os", os_name(system.os_type()))
+            .with_var("distro", system.os_type_raw().to_string().to_kebab_case())
+            .with_var("home", home)
+            .with_var("version", version.name.clone())
+            .with_var("config_dir", config_dir)
+    }
+
+    /// Builds the context for installing `version` on a VPS described by
+    /// `facts`. Paths start at `$HOME`, which the remote shell expands.
+    pub fn for_remote(facts: &RemoteFacts, os: OSType, version: &ApplicationVersion) -> Self {
+        let mut ctx = Self {
+            remote: true,
+            ..Self::new()
+        };
+        for (name, value) in user_variables() {
+            ctx.set(&name, value);
+        }
+
+        ctx.with_var("arch", facts.arch.clone().unwrap_or_default())
+            .with_var("os", os_name(OSType::Linux))
+            .with_var("distro", os.to_string().to_kebab_case())
+            .with_var("home", "$HOME")
+            .with_var("version", version.name.clone())
+            .with_var("config_dir", "$HOME/.config/tranquility")
+    }
+
+    /// Expands every `{{name}}` placeholder in `input`.
+    ///
+    /// Braces that do not wrap a plain identifier (e.g. Go templates such as
+    /// `{{.State.Status}}`) are left untouched.
+    pub fn render(&self, input: &str) -> Result<String, TemplateError> {
+        let mut out = String::with_capacity(input.len());
+        let mut rest = input;
+
+        while let Some(start) = rest.find("{{") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+
+            match placeholder(after) {
+                Some((name, consumed)) => {
+                    let value = self
+                        .get(name)
+                        .ok_or_else(|| TemplateError::UnknownVariable(name.to_owned()))?;
+                    out.push_str(value);
+                    rest = &after[consumed..];
+                }
+                None => {
+                    out.push_str("{{");
+                    rest = after;
+                }
+            }
+        }
+
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+/// Returns the names of all placeholders referenced in `input`.
+pub fn referenced_variables(input: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = input;
+
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        match placeholder(after) {
+            Some((name, consumed)) => {
+                names.push(name);
+                rest = &after[consumed..];
+            }
+            None => rest = after,
+        }
+    }
+
+    names
+}
+
+/// Variables defined in the loaded config, if any.
+pub fn user_variables() -> BTreeMap<String, String> {
+    CONFIG
+        .get()
+        .map(|cfg| cfg.variables.clone())
+        .unwrap_or_default()
+}
+
+/// Parses `name }}` at the start of `input`, returning the trimmed name and
+/// the number of bytes consumed including the closing braces.
+fn placeholder(input: &str) -> Option<(&str, usize)> {
+    let end = input.find("}}")?;
+    let name = input[..end].trim();
+    let valid = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    valid.then_some((name, end + 2))
+}
+
+fn os_name(os: OSType) -> String {
+    match os {
+        OSType::Linux => "linux".to_string(),
+        OSType::Macos => "macos".to_string(),
+        OSType::Windows => "windows".to_string(),
+        other => other.to_string().to_kebab_case(),
+    }
+}
@@ -61,7 +61,7 @@ macro_rules! log {
 
     // with duration
     ($level:expr, $action:expr, $app:expr, $status:expr, $duration:expr) => {{
-        let loaded = $crate::model::config::CONFIG.get().is_some();
+        let loaded = $crate::config::CONFIG.get().is_some();
         let source = if $level == "error" {
             Some(concat!(file!(), ":", line!()))
         } else {
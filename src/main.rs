@@ -1,3 +1,5 @@
+use std::process::ExitCode;
+
 use clap::Parser;
 use tranquility::{
     cli::{TranquilityCommand, handle_command_errors, handle_commands},
@@ -5,15 +7,14 @@ use tranquility::{
     core::print::tranquility_figlet,
 };
 
-fn main() {
+fn main() -> ExitCode {
     config::TranquilityConfig::load_once();
     tranquility_figlet();
     match TranquilityCommand::try_parse() {
-        Ok(args) => {
-            handle_commands(args);
-        }
+        Ok(args) => handle_commands(args),
         Err(err) => {
             handle_command_errors(err);
+            ExitCode::FAILURE
         }
     }
 }
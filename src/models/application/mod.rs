@@ -1,12 +1,19 @@
 pub mod schema;
+pub mod step;
 
 use crate::{
     config::TranquilityConfig,
-    core::shell::command::{command_exists, run_shell_command},
-    log_error,
+    core::{
+        shell::{ShellCommand, command::command_exists},
+        template::{TemplateContext, TemplateError},
+    },
+    log_error, log_info,
     models::{
+        application::step::{InstallStep, StepDecision},
         category::Category,
-        package_manager::PackageManager,
+        package_manager::{InstalledPackages, PackageManager, PackageRequest, version_matches},
+        package_options::PackageOptions,
+        repository::Repository,
         system::{OsSupport, SystemInfo, SystemSupport},
     },
     print_error, print_info,
@@ -17,9 +24,27 @@ use heck::ToKebabCase;
 use os_info::Type as OSType;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::{io, time::Duration};
 use tabled::settings::Style;
 use tabled::{Table, Tabled};
 
+/// How long a check command may run before verification gives up.
+pub const VERIFY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Whether `output` reports `expected` as a whole version, compared as
+/// [`version_matches`] does: `1.2` matches `v1.2` or `1.2-3`, not `1.20`.
+pub fn reports_version(output: &str, expected: &str) -> bool {
+    output
+        .split(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '(' | ')' | '"' | '\''))
+        .map(|token| token.trim_end_matches('.'))
+        .any(|token| {
+            version_matches(token, expected)
+                || token
+                    .strip_prefix('v')
+                    .is_some_and(|token| version_matches(token, expected))
+        })
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(rename = "applications")]
 pub struct ApplicationList {
@@ -45,6 +70,9 @@ pub struct ApplicationVersion {
     pub name: String,
     #[serde(default)]
     pub check_command: Option<String>,
+    /// Version string the check command must print after installing
+    #[serde(default)]
+    pub expected_version: Option<String>,
     #[serde(default)]
     pub dependencies: Vec<String>,
     pub install_methods: Vec<InstallMethod>,
@@ -60,8 +88,17 @@ pub struct InstallMethod {
     pub package_manager: Option<PackageManager>,
     #[serde(default)]
     pub package_name: Option<String>,
+    /// Exact package version to install and hold, e.g. `5:27.3.1-1~ubuntu`
+    #[serde(default)]
+    pub package_version: Option<String>,
     #[serde(default)]
     pub is_cask: Option<bool>,
+    /// Settings for the method's package manager
+    #[serde(default)]
+    pub options: Option<PackageOptions>,
+    /// Vendor repository added before installing and removed on uninstall
+    #[serde(default)]
+    pub repository: Option<Repository>,
     #[serde(default)]
     pub steps: Option<InstallSteps>,
 }
@@ -69,15 +106,15 @@ pub struct InstallMethod {
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct InstallSteps {
     #[serde(default)]
-    pub preinstall_steps: Vec<String>,
+    pub preinstall_steps: Vec<InstallStep>,
     #[serde(default)]
-    pub install: Vec<String>,
+    pub install: Vec<InstallStep>,
     #[serde(default)]
-    pub postinstall_steps: Vec<String>,
+    pub postinstall_steps: Vec<InstallStep>,
     #[serde(default)]
-    pub uninstall: Vec<String>,
+    pub uninstall: Vec<InstallStep>,
     #[serde(default)]
-    pub postuninstall_steps: Vec<String>,
+    pub postuninstall_steps: Vec<InstallStep>,
 }
 
 #[derive(Debug, Tabled)]
@@ -134,43 +171,263 @@ impl Application {
             .unwrap_or(false)
     }
 
+    pub fn prompt_upgrade(&self) -> bool {
+        Confirm::new()
+            .with_prompt(
+                format!("Do you want to upgrade: {}?", self.name)
+                    .purple()
+                    .to_string(),
+            )
+            .default(true)
+            .interact()
+            .unwrap_or(false)
+    }
+
     pub fn is_installed(&self) -> bool {
         self.versions
             .first()
-            .and_then(|v| v.check_command.as_deref())
+            .is_some_and(ApplicationVersion::is_present)
+    }
+
+    /// Picks the first version and install method that targets `system`.
+    pub fn select<'a>(&'a self, system: &SystemInfo) -> Option<SelectedInstall<'a>> {
+        self.select_for(
+            system.os_type_raw(),
+            |_| true,
+            |version| TemplateContext::for_version(system, version),
+        )
+    }
+
+    /// Picks the first version and install method that targets `os` and
+    /// that `usable` accepts, building the template context with `context`.
+    pub fn select_for<'a>(
+        &'a self,
+        os: OSType,
+        usable: impl Fn(&InstallMethod) -> bool,
+        context: impl Fn(&ApplicationVersion) -> TemplateContext,
+    ) -> Option<SelectedInstall<'a>> {
+        self.versions.iter().find_map(|version| {
+            version
+                .install_methods
+                .iter()
+                .filter(|method| method.os.iter().any(|m| m.equals_ostype(&os)))
+                .find(|method| usable(method))
+                .map(|method| SelectedInstall {
+                    app: self,
+                    version,
+                    method,
+                    context: context(version),
+                })
+        })
+    }
+}
+
+/// An app paired with the version and install method chosen for a system.
+pub struct SelectedInstall<'a> {
+    pub app: &'a Application,
+    pub version: &'a ApplicationVersion,
+    pub method: &'a InstallMethod,
+    pub context: TemplateContext,
+}
+
+impl SelectedInstall<'_> {
+    /// The package manager and the name the package is listed under once
+    /// installed, if the method names one.
+    pub fn package(&self) -> Option<(PackageManager, String)> {
+        let pm = self.method.package_manager?;
+        let name = self.method.package_name.as_deref()?;
+        let name = self.context.render(name).ok()?;
+        Some((
+            pm,
+            pm.installed_name(&name, self.method.package_version.as_deref()),
+        ))
+    }
+
+    /// Asks the package manager when the method names a package, and falls
+    /// back to the version's check command otherwise.
+    ///
+    /// A package installed at another version than the method pins counts
+    /// as missing, so the install moves it to the pin.
+    pub fn is_installed(&self, installed: &InstalledPackages) -> bool {
+        self.package()
+            .and_then(|(pm, name)| {
+                let found = installed.contains(pm, &name)?;
+                Some(found && self.matches_pin(pm, &name))
+            })
+            .unwrap_or_else(|| self.version.is_present())
+    }
+
+    /// Brew encodes the pin in the package name, so only other managers
+    /// need their installed version compared.
+    fn matches_pin(&self, pm: PackageManager, name: &str) -> bool {
+        match self.method.package_version.as_deref() {
+            Some(wanted) if pm != PackageManager::Brew => pm
+                .installed_version(name)
+                .is_none_or(|have| version_matches(&have, wanted)),
+            _ => true,
+        }
+    }
+}
+
+impl ApplicationVersion {
+    /// Whether the binary named by `check_command` resolves on the PATH.
+    pub fn is_present(&self) -> bool {
+        self.check_command
+            .as_deref()
+            .and_then(|check| check.split_whitespace().next())
             .is_some_and(command_exists)
     }
+
+    /// Checks that the version is actually present after installing.
+    ///
+    /// The first word of `check_command` must resolve on the PATH, the
+    /// command must exit successfully within [`VERIFY_TIMEOUT`] and, when
+    /// `expected_version` is set, its output must report it.
+    pub fn verify(&self) -> Result<(), String> {
+        let Some(check) = self.check_command.as_deref() else {
+            return Ok(());
+        };
+
+        let binary = check.split_whitespace().next().unwrap_or(check);
+        if !command_exists(binary) {
+            return Err(format!("`{binary}` was not found on the PATH"));
+        }
+
+        let (status, output) = ShellCommand::from_script(check, false)
+            .with_timeout(Some(VERIFY_TIMEOUT))
+            .capture()
+            .map_err(|e| format!("failed to run `{check}`: {e}"))?;
+
+        let printed = output.tail(output.lines.len());
+        if !status.success() {
+            return Err(format!(
+                "`{check}` exited with {} (output: {})",
+                status,
+                printed.trim()
+            ));
+        }
+
+        match self.expected_version.as_deref() {
+            Some(expected) if !reports_version(&printed, expected) => Err(format!(
+                "`{check}` did not report version {expected} (got: {})",
+                printed.trim()
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl InstallSteps {
+    /// Install phases in execution order.
+    pub fn install_phases(&self) -> [(&'static str, &[InstallStep]); 3] {
+        [
+            ("preinstall", &self.preinstall_steps),
+            ("install", &self.install),
+            ("postinstall", &self.postinstall_steps),
+        ]
+    }
+
+    /// Uninstall phases in execution order.
+    pub fn uninstall_phases(&self) -> [(&'static str, &[InstallStep]); 2] {
+        [
+            ("uninstall", &self.uninstall),
+            ("postuninstall", &self.postuninstall_steps),
+        ]
+    }
+}
+
+/// A step together with the decision its guards produced.
+pub struct PlannedStep<'a> {
+    pub phase: &'static str,
+    pub step: &'a InstallStep,
+    pub decision: StepDecision,
+}
+
+#[derive(Tabled)]
+struct DisplayPlannedStep {
+    #[tabled(rename = "#")]
+    index: usize,
+    #[tabled(rename = "Phase")]
+    phase: &'static str,
+    #[tabled(rename = "Step")]
+    step: String,
+    #[tabled(rename = "Action")]
+    action: String,
 }
 
 impl InstallMethod {
-    pub fn install(&self, dry_run: bool) {
-        if let Some(steps) = &self.steps {
-            for cmd in &steps.preinstall_steps {
-                run_shell_command(cmd);
-            }
-            for cmd in &steps.install {
-                run_shell_command(cmd);
-            }
-            for cmd in &steps.postinstall_steps {
-                run_shell_command(cmd);
+    /// Package manager request for the already expanded package name.
+    pub fn package_request<'a>(&'a self, name: &'a str) -> PackageRequest<'a> {
+        PackageRequest::new(name)
+            .with_version(self.package_version.as_deref())
+            .with_cask(self.is_cask.unwrap_or(false))
+            .with_options(self.options.as_ref())
+    }
+
+    /// Holds a pinned package after [`Self::install`]; a no-op for methods
+    /// without a package version.
+    pub fn hold(&self, ctx: &TemplateContext, dry_run: bool) -> io::Result<()> {
+        match (self.package_manager, self.package_name.as_deref()) {
+            (Some(pm), Some(pkg)) if self.steps.is_none() => {
+                let pkg = ctx.render(pkg).map_err(invalid_template)?;
+                pm.hold(None, &self.package_request(&pkg), dry_run)
             }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn install(&self, ctx: &TemplateContext, dry_run: bool) -> io::Result<()> {
+        if let Some(steps) = &self.steps {
+            run_steps(ctx, &steps.install_phases(), dry_run)
         } else if let (Some(pm), Some(pkg)) = (self.package_manager, self.package_name.as_deref()) {
-            pm.install(None, pkg, self.is_cask, dry_run);
+            let pkg = ctx.render(pkg).map_err(invalid_template)?;
+            pm.install(None, &self.package_request(&pkg), dry_run)
         } else {
             print_error!("❌ No install steps or valid package manager fallback provided.");
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "no install steps or package manager",
+            ))
         }
     }
 
-    pub fn uninstall(&self, dry_run: bool) {
-        if let Some(steps) = &self.steps {
-            for cmd in &steps.uninstall {
-                run_shell_command(cmd);
-            }
-            for cmd in &steps.postuninstall_steps {
-                run_shell_command(cmd);
+    /// Removes the package and, unless `keep_repo` is set, the vendor
+    /// repository it was installed from.
+    pub fn uninstall(
+        &self,
+        ctx: &TemplateContext,
+        keep_repo: bool,
+        dry_run: bool,
+    ) -> io::Result<()> {
+        self.remove_package(ctx, dry_run)?;
+
+        match (&self.repository, self.package_manager) {
+            (Some(repo), Some(pm)) if !keep_repo => repo.remove(pm, dry_run),
+            _ => Ok(()),
+        }
+    }
+
+    /// Upgrades the package in place; pinned packages are left alone.
+    /// Step-based methods have no upgrade path of their own.
+    pub fn upgrade(&self, ctx: &TemplateContext, dry_run: bool) -> io::Result<()> {
+        match (self.package_manager, self.package_name.as_deref()) {
+            (Some(pm), Some(pkg)) if self.steps.is_none() => {
+                let pkg = ctx.render(pkg).map_err(invalid_template)?;
+                pm.upgrade(None, &self.package_request(&pkg), dry_run)
             }
+            _ => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "only package manager installs can be upgraded",
+            )),
+        }
+    }
+
+    fn remove_package(&self, ctx: &TemplateContext, dry_run: bool) -> io::Result<()> {
+        if let Some(steps) = &self.steps {
+            run_steps(ctx, &steps.uninstall_phases(), dry_run)
         } else if let (Some(pm), Some(pkg)) = (self.package_manager, self.package_name.as_deref()) {
-            pm.uninstall(None, pkg, dry_run);
+            let pkg = ctx.render(pkg).map_err(invalid_template)?;
+            pm.uninstall(None, &self.package_request(&pkg), dry_run)
         } else {
             print_error!("❌ No uninstall steps or valid package manager fallback provided.");
             log_error!(
@@ -178,10 +435,104 @@ impl InstallMethod {
                 "app",
                 "No uninstall steps or valid package manager fallback provided."
             );
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "no uninstall steps or package manager",
+            ))
         }
     }
 }
 
+/// Evaluates the guards of every step without running anything.
+///
+/// Only `creates` is checked; command guards are listed as "would check".
+/// Guards are evaluated up front, so a step whose guard depends on an
+/// earlier step's effect may be shown as running even though it would be
+/// skipped.
+pub fn plan_steps<'a>(
+    ctx: &TemplateContext,
+    phases: &[(&'static str, &'a [InstallStep])],
+) -> Vec<PlannedStep<'a>> {
+    phases
+        .iter()
+        .flat_map(|(phase, steps)| steps.iter().map(move |step| (*phase, step)))
+        .map(|(phase, step)| PlannedStep {
+            phase,
+            step,
+            decision: step
+                .plan_guards(ctx)
+                .unwrap_or_else(|e| StepDecision::Skip(format!("invalid template: {e}"))),
+        })
+        .collect()
+}
+
+pub fn print_plan(plan: &[PlannedStep]) {
+    let rows: Vec<DisplayPlannedStep> = plan
+        .iter()
+        .enumerate()
+        .map(|(i, planned)| DisplayPlannedStep {
+            index: i + 1,
+            phase: planned.phase,
+            step: planned.step.run().to_string(),
+            action: match &planned.decision {
+                StepDecision::Run => "run".to_string(),
+                StepDecision::Skip(reason) => format!("skip ({reason})"),
+                StepDecision::Check(guards) => format!("would check {guards}"),
+            },
+        })
+        .collect();
+
+    let mut table = Table::new(rows);
+    table.with(Style::modern_rounded());
+    println!("{}", table);
+}
+
+/// Runs each phase in order, skipping guarded steps and stopping at the
+/// first step that fails.
+///
+/// In dry-run mode only the plan is printed.
+fn run_steps(
+    ctx: &TemplateContext,
+    phases: &[(&'static str, &[InstallStep])],
+    dry_run: bool,
+) -> io::Result<()> {
+    if dry_run {
+        print_info!("💡 [Dry Run] Step plan:");
+        print_plan(&plan_steps(ctx, phases));
+        return Ok(());
+    }
+
+    for step in phases.iter().flat_map(|(_, steps)| steps.iter()) {
+        let result = step
+            .check_guards(ctx)
+            .map_err(invalid_template)
+            .and_then(|decision| match decision {
+                StepDecision::Skip(reason) => {
+                    print_info!("⏭️  Skipping '{}': {}", step.run(), reason);
+                    log_info!("step", step.run(), &format!("skipped: {reason}"));
+                    Ok(())
+                }
+                // check_guards evaluates command guards itself
+                StepDecision::Run | StepDecision::Check(_) => step
+                    .to_command(ctx)
+                    .map_err(invalid_template)
+                    .and_then(|cmd| cmd.run(false)),
+            });
+
+        if let Err(e) = result {
+            print_error!("❌ Step '{}' failed: {}", step.run(), e);
+            log_error!("step", step.run(), &e.to_string());
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
+
+fn invalid_template(e: TemplateError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, e)
+}
+
 pub fn get_apps() -> ApplicationList {
     let mut apps = default_apps();
     let config = TranquilityConfig::load_once();
@@ -273,13 +624,17 @@ fn default_apps() -> Vec<Application> {
             vec![ApplicationVersion {
                 name: "Latest".to_string(),
                 check_command: Some("alacritty --version".to_string()),
+                expected_version: None,
                 dependencies: vec!["cmake".to_string()],
                 install_methods: vec![InstallMethod {
                     fallback: false,
                     os: vec![OSType::Ubuntu.into(), OSType::Debian.into()],
                     package_manager: Some(PackageManager::Apt),
                     package_name: Some("alacritty".to_string()),
+                    package_version: None,
                     is_cask: None,
+                    options: None,
+                    repository: None,
                     steps: None,
                 }],
             }],
@@ -293,13 +648,17 @@ fn default_apps() -> Vec<Application> {
             vec![ApplicationVersion {
                 name: "Default".to_string(),
                 check_command: Some("fish --version".to_string()),
+                expected_version: None,
                 dependencies: vec![],
                 install_methods: vec![InstallMethod {
                     fallback: false,
                     os: vec![OSType::Linux.into(), OSType::Macos.into()],
                     package_manager: Some(PackageManager::Apt),
                     package_name: Some("fish".to_string()),
+                    package_version: None,
                     is_cask: None,
+                    options: None,
+                    repository: None,
                     steps: None,
                 }],
             }],
@@ -313,13 +672,17 @@ fn default_apps() -> Vec<Application> {
             vec![ApplicationVersion {
                 name: "Default".to_string(),
                 check_command: Some("zsh --version".to_string()),
+                expected_version: None,
                 dependencies: vec![],
                 install_methods: vec![InstallMethod {
                     fallback: false,
                     os: vec![OSType::Linux.into(), OSType::Macos.into()],
                     package_manager: Some(PackageManager::Apt),
                     package_name: Some("zsh".to_string()),
+                    package_version: None,
                     is_cask: None,
+                    options: None,
+                    repository: None,
                     steps: None,
                 }],
             }],
@@ -1,5 +1,8 @@
 use crate::{
-    SUPPORTED_EXTS, log_info, log_warn, models::application::ApplicationList as ApplicationFile,
+    SUPPORTED_EXTS,
+    core::template::{BUILTIN_VARIABLES, referenced_variables, user_variables},
+    log_info, log_warn,
+    models::{application::ApplicationList as ApplicationFile, package_manager::PackageManager},
 };
 use jsonschema::validator_for;
 use schemars::schema_for;
@@ -138,6 +141,7 @@ pub fn validate_file(path: &Path) -> bool {
 
 fn validate_custom(json: &Value) -> Result<(), String> {
     let mut errors = Vec::new();
+    let user_vars = user_variables();
 
     if let Some(apps) = json.get("applications").and_then(|v| v.as_array()) {
         for (i, app) in apps.iter().enumerate() {
@@ -179,6 +183,82 @@ fn validate_custom(json: &Value) -> Result<(), String> {
                                     ));
                                 }
                             }
+
+                            if method.get("package_version").is_some() {
+                                if !has_pkg_name {
+                                    errors.push(format!(
+                                        "App[{}] Version[{}] Method[{}]: 'package_version' requires 'package_name'",
+                                        i, j, k
+                                    ));
+                                }
+                                let pm = method.get("package_manager").and_then(|v| {
+                                    serde_json::from_value::<PackageManager>(v.clone()).ok()
+                                });
+                                if let Some(pm) = pm
+                                    && !pm.supports_version_pinning()
+                                {
+                                    errors.push(format!(
+                                        "App[{}] Version[{}] Method[{}]: {} does not support 'package_version'",
+                                        i, j, k,
+                                        pm.name()
+                                    ));
+                                }
+                            }
+
+                            if let Some(options) = method.get("options").and_then(|v| v.as_object())
+                            {
+                                let pm = method.get("package_manager").and_then(|v| v.as_str());
+                                for key in options.keys() {
+                                    if pm.is_none_or(|pm| !pm.eq_ignore_ascii_case(key)) {
+                                        errors.push(format!(
+                                            "App[{}] Version[{}] Method[{}]: '{}' options do not match package_manager {}",
+                                            i, j, k, key,
+                                            pm.unwrap_or("(none)")
+                                        ));
+                                    }
+                                }
+                            }
+
+                            if let Some(repo) = method.get("repository").filter(|v| !v.is_null()) {
+                                let pm = method.get("package_manager").and_then(|v| v.as_str());
+                                let has = |key: &str| repo.get(key).is_some_and(|v| !v.is_null());
+                                let problem = match pm {
+                                    Some("Apt") if !has("url") => {
+                                        Some("apt repositories require 'url'")
+                                    }
+                                    Some("Apt") => None,
+                                    Some("Dnf" | "Yum")
+                                        if !(has("url") || has("repo_file") || has("copr")) =>
+                                    {
+                                        Some(
+                                            "dnf repositories require 'url', 'repo_file' or 'copr'",
+                                        )
+                                    }
+                                    Some("Dnf" | "Yum") => None,
+                                    _ => Some(
+                                        "'repository' requires package_manager Apt, Dnf or Yum",
+                                    ),
+                                };
+                                if let Some(problem) = problem {
+                                    errors.push(format!(
+                                        "App[{}] Version[{}] Method[{}]: {}",
+                                        i, j, k, problem
+                                    ));
+                                }
+                            }
+
+                            for text in templated_fields(method) {
+                                for name in referenced_variables(text) {
+                                    if !BUILTIN_VARIABLES.contains(&name)
+                                        && !user_vars.contains_key(name)
+                                    {
+                                        errors.push(format!(
+                                            "App[{}] Version[{}] Method[{}]: Unknown template variable '{{{{{}}}}}' in '{}'",
+                                            i, j, k, name, text
+                                        ));
+                                    }
+                                }
+                            }
                         }
                     }
                 }
@@ -192,3 +272,43 @@ fn validate_custom(json: &Value) -> Result<(), String> {
         Err(errors.join("\n"))
     }
 }
+
+/// Strings of an install method that are expanded as templates before use.
+fn templated_fields(method: &Value) -> Vec<&str> {
+    let mut fields: Vec<&str> = method
+        .get("package_name")
+        .and_then(|v| v.as_str())
+        .into_iter()
+        .collect();
+
+    if let Some(repo) = method.get("repository") {
+        fields.extend(
+            ["key_url", "url", "suite", "repo_file"]
+                .iter()
+                .filter_map(|k| repo.get(*k)?.as_str()),
+        );
+    }
+
+    if let Some(steps) = method.get("steps").and_then(|s| s.as_object()) {
+        for step in steps.values().filter_map(|v| v.as_array()).flatten() {
+            match step {
+                // Plain command string
+                Value::String(cmd) => fields.push(cmd),
+                // Detailed step: commands, paths and `env` values are expanded
+                Value::Object(spec) => {
+                    fields.extend(
+                        ["run", "cwd", "creates", "only_if", "unless"]
+                            .iter()
+                            .filter_map(|k| spec.get(*k)?.as_str()),
+                    );
+                    if let Some(env) = spec.get("env").and_then(|e| e.as_object()) {
+                        fields.extend(env.values().filter_map(|v| v.as_str()));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fields
+}
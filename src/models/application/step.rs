@@ -0,0 +1,257 @@
+// Module: Model/Application/Step
+// Location: cli/src/model/application/step.rs
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::core::{
+    expand_home,
+    shell::ShellCommand,
+    template::{TemplateContext, TemplateError},
+};
+
+/// A single install or uninstall step.
+///
+/// Steps are either a plain command string, which runs through the default
+/// shell as the invoking user, or an object with extra execution settings.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum InstallStep {
+    Command(String),
+    Detailed(StepSpec),
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct StepSpec {
+    /// Command to run
+    pub run: String,
+    /// Extra environment variables for the command
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Working directory for the command
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Run the command with elevated privileges
+    #[serde(default)]
+    pub sudo: bool,
+    /// Kill the command after this many seconds
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    /// Shell used to run the command, e.g. `bash` or `pwsh`
+    #[serde(default)]
+    pub shell: Option<String>,
+    /// Skip the step when this path already exists
+    #[serde(default)]
+    pub creates: Option<String>,
+    /// Only run the step if this command succeeds
+    #[serde(default)]
+    pub only_if: Option<String>,
+    /// Skip the step if this command succeeds
+    #[serde(default)]
+    pub unless: Option<String>,
+}
+
+/// A step's guards, expanded so they can be evaluated where the step runs.
+#[derive(Debug, Default)]
+pub struct StepGuards {
+    /// Skip the step when this path exists
+    pub creates: Option<String>,
+    /// Must succeed for the step to run
+    pub only_if: Option<ShellCommand>,
+    /// Must fail for the step to run
+    pub unless: Option<ShellCommand>,
+}
+
+/// Whether a step should run, based on its guards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepDecision {
+    Run,
+    Skip(String),
+    /// Dry-run only: the step runs if these command guards pass
+    Check(String),
+}
+
+impl From<&str> for InstallStep {
+    fn from(cmd: &str) -> Self {
+        InstallStep::Command(cmd.to_string())
+    }
+}
+
+impl InstallStep {
+    /// The raw command, before template expansion.
+    pub fn run(&self) -> &str {
+        match self {
+            InstallStep::Command(cmd) => cmd,
+            InstallStep::Detailed(spec) => &spec.run,
+        }
+    }
+
+    /// Evaluates `creates`, `only_if` and `unless`, in that order.
+    ///
+    /// Guard commands run quietly with the step's shell, env and working
+    /// directory, but never with elevated privileges.
+    pub fn check_guards(&self, ctx: &TemplateContext) -> Result<StepDecision, TemplateError> {
+        let InstallStep::Detailed(spec) = self else {
+            return Ok(StepDecision::Run);
+        };
+
+        if let Some(ref path) = spec.creates {
+            let path = expand_home(&ctx.render(path)?);
+            if Path::new(&path).exists() {
+                return Ok(StepDecision::Skip(format!("{path} already exists")));
+            }
+        }
+
+        if let Some(ref cmd) = spec.only_if
+            && !spec.guard_command(cmd, ctx)?.succeeds()
+        {
+            return Ok(StepDecision::Skip(format!("only_if `{cmd}` failed")));
+        }
+
+        if let Some(ref cmd) = spec.unless
+            && spec.guard_command(cmd, ctx)?.succeeds()
+        {
+            return Ok(StepDecision::Skip(format!("unless `{cmd}` succeeded")));
+        }
+
+        Ok(StepDecision::Run)
+    }
+
+    /// Like [`Self::check_guards`], but without side effects: `creates` is
+    /// checked, while `only_if` and `unless` are reported instead of run.
+    /// On a VPS, `creates` is reported too.
+    pub fn plan_guards(&self, ctx: &TemplateContext) -> Result<StepDecision, TemplateError> {
+        let InstallStep::Detailed(spec) = self else {
+            return Ok(StepDecision::Run);
+        };
+
+        let mut guards = Vec::new();
+        if let Some(ref path) = spec.creates {
+            if ctx.is_remote() {
+                guards.push(format!("creates `{}`", ctx.render(path)?));
+            } else {
+                let path = expand_home(&ctx.render(path)?);
+                if Path::new(&path).exists() {
+                    return Ok(StepDecision::Skip(format!("{path} already exists")));
+                }
+            }
+        }
+
+        guards.extend(spec.only_if.iter().map(|cmd| format!("only_if `{cmd}`")));
+        guards.extend(spec.unless.iter().map(|cmd| format!("unless `{cmd}`")));
+        if guards.is_empty() {
+            Ok(StepDecision::Run)
+        } else {
+            Ok(StepDecision::Check(guards.join(", ")))
+        }
+    }
+
+    /// The guards with templates expanded, for evaluating them elsewhere
+    /// than [`Self::check_guards`] does, e.g. in a script run on a VPS.
+    pub fn guards(&self, ctx: &TemplateContext) -> Result<StepGuards, TemplateError> {
+        let InstallStep::Detailed(spec) = self else {
+            return Ok(StepGuards::default());
+        };
+
+        Ok(StepGuards {
+            creates: spec
+                .creates
+                .as_deref()
+                .map(|path| ctx.render(path).map(|path| local_path(&path, ctx)))
+                .transpose()?,
+            only_if: spec
+                .only_if
+                .as_deref()
+                .map(|cmd| spec.guard_command(cmd, ctx))
+                .transpose()?,
+            unless: spec
+                .unless
+                .as_deref()
+                .map(|cmd| spec.guard_command(cmd, ctx))
+                .transpose()?,
+        })
+    }
+
+    /// Expands templates and builds the command that executes this step.
+    pub fn to_command(&self, ctx: &TemplateContext) -> Result<ShellCommand, TemplateError> {
+        let spec = match self {
+            InstallStep::Command(cmd) => {
+                return Ok(script_command(&ctx.render(cmd)?, None, false, ctx));
+            }
+            InstallStep::Detailed(spec) => spec,
+        };
+
+        let run = ctx.render(&spec.run)?;
+        let cmd = script_command(&run, spec.shell.as_deref(), spec.sudo, ctx);
+
+        let cwd = spec
+            .cwd
+            .as_deref()
+            .map(|dir| {
+                ctx.render(dir)
+                    .map(|dir| PathBuf::from(local_path(&dir, ctx)))
+            })
+            .transpose()?;
+
+        let env = spec
+            .env
+            .iter()
+            .map(|(k, v)| ctx.render(v).map(|v| (k.clone(), v)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(cmd
+            .with_env(env)
+            .with_cwd(cwd)
+            .with_timeout(spec.timeout.map(Duration::from_secs)))
+    }
+}
+
+impl StepSpec {
+    /// A guard command runs with the step's shell, env and working
+    /// directory, but never with elevated privileges.
+    fn guard_command(
+        &self,
+        cmd: &str,
+        ctx: &TemplateContext,
+    ) -> Result<ShellCommand, TemplateError> {
+        InstallStep::Detailed(StepSpec {
+            run: cmd.to_string(),
+            env: self.env.clone(),
+            cwd: self.cwd.clone(),
+            timeout: self.timeout,
+            shell: self.shell.clone(),
+            ..StepSpec::default()
+        })
+        .to_command(ctx)
+    }
+}
+
+/// Runs `run` through `shell`, defaulting to `sh` on a VPS since the local
+/// default may be PowerShell.
+fn script_command(
+    run: &str,
+    shell: Option<&str>,
+    sudo: bool,
+    ctx: &TemplateContext,
+) -> ShellCommand {
+    match shell {
+        Some(shell) => ShellCommand::from_script_with_shell(run, shell, sudo),
+        None if ctx.is_remote() => ShellCommand::from_script_with_shell(run, "sh", sudo),
+        None => ShellCommand::from_script(run, sudo),
+    }
+}
+
+/// Expands `~` locally; on a VPS the remote shell does it.
+fn local_path(path: &str, ctx: &TemplateContext) -> String {
+    if ctx.is_remote() {
+        path.to_string()
+    } else {
+        expand_home(path)
+    }
+}
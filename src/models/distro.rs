@@ -0,0 +1,163 @@
+// Module: Model/Distro
+// Location: cli/src/model/distro.rs
+use std::collections::HashMap;
+
+use strum::Display;
+
+use crate::models::{
+    package_manager::{PackageManager, PackageManager as Pm},
+    system::os_release,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum DistroFamily {
+    Debian,
+    RedHat,
+    Suse,
+    Arch,
+    Alpine,
+    Gentoo,
+    Void,
+    NixOS,
+}
+
+/// Tools tranquility may need to install before it can use a manager.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BootstrapTool {
+    Snap,
+    Flatpak,
+}
+
+impl DistroFamily {
+    /// Package providing `tool` in this family's default repositories.
+    pub fn bootstrap_package(&self, tool: BootstrapTool) -> Option<&'static str> {
+        use BootstrapTool::*;
+        use DistroFamily::*;
+        match (self, tool) {
+            (NixOS, _) => None,
+            (Debian | RedHat | Suse, Snap) => Some("snapd"),
+            // snapd is only packaged in the AUR, Alpine, Gentoo and Void
+            (_, Snap) => None,
+            (Gentoo, Flatpak) => Some("sys-apps/flatpak"),
+            (_, Flatpak) => Some("flatpak"),
+        }
+    }
+}
+
+/// What tranquility knows about a Linux distribution.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Distro {
+    /// os-release `ID` this entry matches, directly or through `ID_LIKE`
+    pub id: &'static str,
+    pub family: DistroFamily,
+    pub default_package_manager: PackageManager,
+    /// Managers that can be used on the distro, in order of preference
+    pub package_managers: &'static [PackageManager],
+}
+
+/// Known distributions, keyed by os-release `ID`.
+///
+/// Derivatives such as Mint, Pop!_OS, Rocky or Manjaro are matched through
+/// their `ID_LIKE`, so only distributions others build on need an entry.
+pub const DISTROS: &[Distro] = &[
+    Distro {
+        id: "ubuntu",
+        family: DistroFamily::Debian,
+        default_package_manager: Pm::Apt,
+        package_managers: &[Pm::Apt, Pm::Snap, Pm::Flatpak, Pm::Nix],
+    },
+    Distro {
+        id: "debian",
+        family: DistroFamily::Debian,
+        default_package_manager: Pm::Apt,
+        package_managers: &[Pm::Apt, Pm::Flatpak, Pm::Snap, Pm::Nix],
+    },
+    Distro {
+        id: "fedora",
+        family: DistroFamily::RedHat,
+        default_package_manager: Pm::Dnf,
+        package_managers: &[Pm::Dnf, Pm::Flatpak, Pm::Snap, Pm::Nix],
+    },
+    Distro {
+        id: "rhel",
+        family: DistroFamily::RedHat,
+        default_package_manager: Pm::Dnf,
+        package_managers: &[Pm::Dnf, Pm::Yum, Pm::Flatpak, Pm::Snap, Pm::Nix],
+    },
+    Distro {
+        id: "centos",
+        family: DistroFamily::RedHat,
+        default_package_manager: Pm::Dnf,
+        package_managers: &[Pm::Dnf, Pm::Yum, Pm::Flatpak, Pm::Snap, Pm::Nix],
+    },
+    Distro {
+        id: "suse",
+        family: DistroFamily::Suse,
+        default_package_manager: Pm::Zypper,
+        package_managers: &[Pm::Zypper, Pm::Flatpak, Pm::Snap, Pm::Nix],
+    },
+    Distro {
+        id: "opensuse",
+        family: DistroFamily::Suse,
+        default_package_manager: Pm::Zypper,
+        package_managers: &[Pm::Zypper, Pm::Flatpak, Pm::Snap, Pm::Nix],
+    },
+    Distro {
+        id: "arch",
+        family: DistroFamily::Arch,
+        default_package_manager: Pm::Pacman,
+        package_managers: &[Pm::Pacman, Pm::Aur, Pm::Yay, Pm::Flatpak, Pm::Snap, Pm::Nix],
+    },
+    Distro {
+        id: "alpine",
+        family: DistroFamily::Alpine,
+        default_package_manager: Pm::Apk,
+        package_managers: &[Pm::Apk, Pm::Flatpak, Pm::Nix],
+    },
+    Distro {
+        id: "gentoo",
+        family: DistroFamily::Gentoo,
+        default_package_manager: Pm::Portage,
+        package_managers: &[Pm::Portage, Pm::Flatpak, Pm::Nix],
+    },
+    Distro {
+        id: "void",
+        family: DistroFamily::Void,
+        default_package_manager: Pm::Xbps,
+        package_managers: &[Pm::Xbps, Pm::Flatpak, Pm::Nix],
+    },
+    Distro {
+        id: "nixos",
+        family: DistroFamily::NixOS,
+        default_package_manager: Pm::Nix,
+        package_managers: &[Pm::Nix, Pm::Flatpak],
+    },
+];
+
+impl Distro {
+    /// Finds the entry for `id`, falling back to each `ID_LIKE` in order.
+    pub fn lookup(id: &str, id_like: &[&str]) -> Option<&'static Distro> {
+        std::iter::once(id)
+            .chain(id_like.iter().copied())
+            .find_map(|id| DISTROS.iter().find(|d| d.id.eq_ignore_ascii_case(id)))
+    }
+
+    /// Resolves parsed os-release fields to a table entry.
+    pub fn from_os_release(release: &HashMap<String, String>) -> Option<&'static Distro> {
+        let id = release.get("ID").map(String::as_str).unwrap_or_default();
+        let id_like: Vec<&str> = release
+            .get("ID_LIKE")
+            .map(|like| like.split_whitespace().collect())
+            .unwrap_or_default();
+        Self::lookup(id, &id_like)
+    }
+
+    /// The entry for the running system, if it is a known Linux distribution.
+    pub fn detect() -> Option<&'static Distro> {
+        if cfg!(target_os = "linux") {
+            Self::from_os_release(&os_release())
+        } else {
+            None
+        }
+    }
+}
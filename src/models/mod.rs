@@ -1,6 +1,9 @@
 pub mod application;
 pub mod category;
+pub mod distro;
 pub mod font;
 pub mod package_manager;
+pub mod package_options;
+pub mod repository;
 pub mod system;
 pub mod vps;
@@ -1,8 +1,19 @@
 // Module: Model/PackageManager
 // Location: cli/src/model/package_manager.rs
 use crate::{
-    core::shell::command::{check_command, command_exists, execute_package_cmd, run_shell_command},
-    models::system::SystemInfo,
+    core::aur::{self, AurBuilder, detect_helper},
+    core::bootstrap::{self, Installer},
+    core::retry::Retry,
+    core::shell::{
+        ShellCommand,
+        command::{check_command, command_exists},
+    },
+    models::{
+        distro::{BootstrapTool, Distro},
+        package_options::PackageOptions,
+        repository::refresh_index,
+        system::SystemInfo,
+    },
     print_error, print_warn,
 };
 
@@ -11,8 +22,13 @@ use dialoguer::Confirm;
 use os_info::Type as OSType;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    sync::OnceLock,
+};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema)]
 pub enum PackageManager {
     Apt,
     Snap,
@@ -24,6 +40,10 @@ pub enum PackageManager {
     Apk,
     Pacman,
     Yay,
+    /// AUR packages, through paru/yay when installed or built with makepkg
+    Aur,
+    /// Void Linux
+    Xbps,
     Flatpak,
     Brew,
     Choco,
@@ -31,6 +51,47 @@ pub enum PackageManager {
     Scoop,
 }
 
+/// A package to install or remove through a [`PackageManager`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PackageRequest<'a> {
+    pub name: &'a str,
+    /// Exact version to install and hold, in the manager's version format
+    pub version: Option<&'a str>,
+    /// Install as a Homebrew cask
+    pub cask: bool,
+    pub options: Option<&'a PackageOptions>,
+}
+
+impl<'a> PackageRequest<'a> {
+    pub fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_version(mut self, version: Option<&'a str>) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub fn with_cask(mut self, cask: bool) -> Self {
+        self.cask = cask;
+        self
+    }
+
+    pub fn with_options(mut self, options: Option<&'a PackageOptions>) -> Self {
+        self.options = options;
+        self
+    }
+
+    fn flatpak_user(&self) -> bool {
+        self.options
+            .and_then(|o| o.flatpak.as_ref())
+            .is_some_and(|f| f.user)
+    }
+}
+
 macro_rules! pm_installer {
     ($name:expr, $install_fn:ident) => {{
         if command_exists($name) {
@@ -59,20 +120,11 @@ macro_rules! pm_installer {
 }
 
 impl PackageManager {
+    /// Managers usable on `os`. Linux distributions are resolved through
+    /// the distro table; see [`SystemInfo::supported_package_managers`].
     pub fn supported_on_os(os: OSType) -> Vec<Self> {
         use PackageManager::*;
         match os {
-            OSType::Ubuntu | OSType::Debian | OSType::Pop | OSType::Linux => {
-                vec![Apt, Snap, Flatpak, Nix]
-            }
-            OSType::Fedora => vec![Dnf, Snap, Flatpak, Nix],
-            OSType::Redhat => vec![Yum, Nix],
-            OSType::Alpine => vec![Apk, Nix],
-            OSType::Arch | OSType::Manjaro | OSType::EndeavourOS => {
-                vec![Pacman, Yay, Flatpak, Snap, Nix]
-            }
-            OSType::SUSE => vec![Zypper, Nix],
-            OSType::Gentoo => vec![Portage, Nix],
             OSType::Macos => vec![Brew, Nix],
             OSType::Windows => vec![Winget, Choco, Scoop, Nix],
             _ => vec![],
@@ -90,6 +142,8 @@ impl PackageManager {
             Apk => "apk",
             Pacman => "pacman",
             Yay => "yay",
+            Aur => "aur",
+            Xbps => "xbps",
             Nix => "nix",
             Flatpak => "flatpak",
             Snap => "snap",
@@ -101,13 +155,19 @@ impl PackageManager {
     }
 
     pub fn check_installed(&self) -> bool {
-        command_exists(self.name())
+        match self {
+            // AUR packages are registered with pacman once built
+            Self::Aur => command_exists("pacman"),
+            Self::Xbps => command_exists("xbps-install"),
+            _ => command_exists(self.name()),
+        }
     }
 
     pub fn check_install(&self) -> bool {
         use PackageManager::*;
         match self {
             Yay => pm_installer!("yay", install_yay),
+            Aur => aur_available(),
             Nix => pm_installer!("nix", install_nix),
             Flatpak => pm_installer!("flatpak", install_flatpak),
             Snap => pm_installer!("snap", install_snap),
@@ -118,50 +178,298 @@ impl PackageManager {
         }
     }
 
+    /// Whether `package_version` can be honoured by this manager.
+    pub fn supports_version_pinning(&self) -> bool {
+        use PackageManager::*;
+        matches!(
+            self,
+            Apt | Dnf | Yum | Zypper | Apk | Portage | Brew | Choco | Winget | Scoop
+        )
+    }
+
+    /// Package argument in the manager's own syntax for an exact version,
+    /// e.g. `pkg=ver` for apt or `pkg@ver` for brew.
+    pub fn versioned_package(&self, name: &str, version: Option<&str>) -> io::Result<String> {
+        use PackageManager::*;
+        let Some(version) = version else {
+            return Ok(name.to_string());
+        };
+
+        match self {
+            Apt | Zypper | Apk => Ok(format!("{name}={version}")),
+            Dnf | Yum => Ok(format!("{name}-{version}")),
+            Portage => Ok(format!("={name}-{version}")),
+            Brew | Scoop => Ok(format!("{name}@{version}")),
+            // Passed separately with --version
+            Choco | Winget => Ok(name.to_string()),
+            _ => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("{} does not support pinning package versions", self.name()),
+            )),
+        }
+    }
+
+    /// Name the package is listed under once installed.
+    pub fn installed_name(&self, name: &str, version: Option<&str>) -> String {
+        match (self, version) {
+            (Self::Brew, Some(version)) => format!("{name}@{version}"),
+            _ => name.to_string(),
+        }
+    }
+
+    /// Builds the command that installs `request`, without running it.
+    pub fn install_command(&self, request: &PackageRequest) -> io::Result<ShellCommand> {
+        use PackageManager::*;
+        let package = self.versioned_package(request.name, request.version)?;
+        let package = package.as_str();
+
+        let options = request.options.cloned().unwrap_or_default();
+        let scope = if request.flatpak_user() {
+            "--user"
+        } else {
+            "--system"
+        };
+        let remote = options
+            .flatpak
+            .as_ref()
+            .and_then(|f| f.remote.as_deref())
+            .unwrap_or("flathub");
+
+        let (cmd, mut args): (&str, Vec<&str>) = match self {
+            Apt if options.apt.is_some_and(|a| a.no_install_recommends) => (
+                self.name(),
+                vec!["install", "--no-install-recommends", package, "-y"],
+            ),
+            Apt | Dnf | Yum => (self.name(), vec!["install", package, "-y"]),
+            Zypper => (self.name(), vec!["install", "-y", package]),
+            Pacman | Yay => (self.name(), vec!["-S", package, "--noconfirm"]),
+            Aur => {
+                return AurBuilder::new().helper_command(package).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        format!("no AUR helper installed to install '{package}'"),
+                    )
+                });
+            }
+            Portage => ("emerge", vec![package]),
+            Apk => ("apk", vec!["add", package]),
+            Xbps => ("xbps-install", vec!["-y", package]),
+            Flatpak => ("flatpak", vec!["install", "-y", scope, remote, package]),
+            Snap => ("snap", vec!["install", package]),
+            Brew if request.cask => ("brew", vec!["install", "--cask", package]),
+            Brew => ("brew", vec!["install", package]),
+            Winget => ("winget", vec!["install", package]),
+            Choco => ("choco", vec!["install", package, "-y"]),
+            Scoop => ("scoop", vec!["install", package]),
+            Nix => return Ok(nix_command("install", package)),
+        };
+
+        if let (Choco | Winget, Some(version)) = (self, request.version) {
+            args.extend(["--version", version]);
+        }
+
+        // A pin may sit below the installed version, so allow downgrades
+        if request.version.is_some() {
+            match self {
+                Apt => args.insert(args.len() - 1, "--allow-downgrades"),
+                Zypper => args.insert(1, "--oldpackage"),
+                Choco => args.push("--allow-downgrade"),
+                _ => {}
+            }
+        }
+
+        let channel;
+        if let (Snap, Some(snap)) = (self, &options.snap) {
+            if snap.classic {
+                args.push("--classic");
+            }
+            if let Some(ref ch) = snap.channel {
+                channel = format!("--channel={ch}");
+                args.push(&channel);
+            }
+        }
+
+        Ok(ShellCommand::new(cmd)
+            .with_args(args)
+            .with_sudo(self.needs_sudo(request)))
+    }
+
+    /// Commands that must run before installing `request`, such as adding
+    /// a Homebrew tap.
+    pub fn prepare_commands(&self, request: &PackageRequest) -> Vec<ShellCommand> {
+        let tap = request
+            .options
+            .and_then(|o| o.brew.as_ref())
+            .and_then(|b| b.tap.as_deref());
+
+        match (self, tap) {
+            (Self::Brew, Some(tap)) => vec![ShellCommand::new("brew").with_args(["tap", tap])],
+            _ => vec![],
+        }
+    }
+
+    /// Builds the command that removes `request`, without running it.
+    pub fn uninstall_command(&self, request: &PackageRequest) -> io::Result<ShellCommand> {
+        use PackageManager::*;
+        let package = self.installed_name(request.name, request.version);
+        let package = package.as_str();
+
+        let (cmd, args): (&str, Vec<&str>) = match self {
+            Apt | Dnf | Yum => (self.name(), vec!["remove", package, "-y"]),
+            Zypper => ("zypper", vec!["remove", "-y", package]),
+            Pacman | Yay => (self.name(), vec!["-R", package, "--noconfirm"]),
+            Aur => ("pacman", vec!["-Rns", package, "--noconfirm"]),
+            Portage => ("emerge", vec!["-C", package]),
+            Apk => ("apk", vec!["del", package]),
+            Xbps => ("xbps-remove", vec!["-y", package]),
+            Flatpak if request.flatpak_user() => {
+                ("flatpak", vec!["uninstall", "-y", "--user", package])
+            }
+            Flatpak => ("flatpak", vec!["uninstall", "-y", package]),
+            Snap => ("snap", vec!["remove", package]),
+            Brew => ("brew", vec!["uninstall", package]),
+            Choco => ("choco", vec!["uninstall", package, "-y"]),
+            Winget => ("winget", vec!["uninstall", package]),
+            Scoop => ("scoop", vec!["uninstall", package]),
+            Nix => return Ok(nix_command("remove", package)),
+        };
+
+        Ok(ShellCommand::new(cmd)
+            .with_args(args)
+            .with_sudo(self.needs_sudo(request)))
+    }
+
+    /// Builds the command that upgrades an installed `request` in place.
+    pub fn upgrade_command(&self, request: &PackageRequest) -> io::Result<ShellCommand> {
+        use PackageManager::*;
+        let package = self.installed_name(request.name, request.version);
+        let package = package.as_str();
+
+        let (cmd, args): (&str, Vec<&str>) = match self {
+            Apt => ("apt", vec!["install", "--only-upgrade", package, "-y"]),
+            Dnf | Yum => (self.name(), vec!["upgrade", package, "-y"]),
+            Zypper => ("zypper", vec!["update", "-y", package]),
+            Pacman | Yay => (self.name(), vec!["-S", package, "--noconfirm"]),
+            Aur => return self.install_command(request),
+            Portage => ("emerge", vec!["--update", package]),
+            Apk => ("apk", vec!["add", "--upgrade", package]),
+            Xbps => ("xbps-install", vec!["-yu", package]),
+            Flatpak if request.flatpak_user() => {
+                ("flatpak", vec!["update", "-y", "--user", package])
+            }
+            Flatpak => ("flatpak", vec!["update", "-y", package]),
+            Snap => ("snap", vec!["refresh", package]),
+            Brew if request.cask => ("brew", vec!["upgrade", "--cask", package]),
+            Brew => ("brew", vec!["upgrade", package]),
+            Choco => ("choco", vec!["upgrade", package, "-y"]),
+            Winget => ("winget", vec!["upgrade", package]),
+            Scoop => ("scoop", vec!["update", package]),
+            Nix => return Ok(nix_command("upgrade", package)),
+        };
+
+        Ok(ShellCommand::new(cmd)
+            .with_args(args)
+            .with_sudo(self.needs_sudo(request)))
+    }
+
+    /// Command that stops system updates from moving a pinned package.
+    ///
+    /// apk needs none: `pkg=ver` is recorded in the world file and held.
+    pub fn hold_command(&self, request: &PackageRequest) -> Option<ShellCommand> {
+        use PackageManager::*;
+        request.version?;
+        let package = self.installed_name(request.name, request.version);
+
+        let (cmd, args): (&str, Vec<&str>) = match self {
+            Apt => ("apt-mark", vec!["hold", &package]),
+            Dnf | Yum => (self.name(), vec!["versionlock", "add", &package]),
+            Zypper => ("zypper", vec!["addlock", &package]),
+            Brew if !request.cask => ("brew", vec!["pin", &package]),
+            Choco => ("choco", vec!["pin", "add", "-n", &package]),
+            Winget => ("winget", vec!["pin", "add", "--exact", "--id", &package]),
+            Scoop => ("scoop", vec!["hold", &package]),
+            _ => return None,
+        };
+
+        Some(
+            ShellCommand::new(cmd)
+                .with_args(args)
+                .with_sudo(self.needs_sudo(request)),
+        )
+    }
+
+    /// Command that releases a hold added by [`Self::hold_command`].
+    pub fn unhold_command(&self, request: &PackageRequest) -> Option<ShellCommand> {
+        use PackageManager::*;
+        request.version?;
+        let package = self.installed_name(request.name, request.version);
+
+        let (cmd, args): (&str, Vec<&str>) = match self {
+            Apt => ("apt-mark", vec!["unhold", &package]),
+            Dnf | Yum => (self.name(), vec!["versionlock", "delete", &package]),
+            Zypper => ("zypper", vec!["removelock", &package]),
+            Brew if !request.cask => ("brew", vec!["unpin", &package]),
+            Choco => ("choco", vec!["pin", "remove", "-n", &package]),
+            Winget => ("winget", vec!["pin", "remove", "--exact", "--id", &package]),
+            Scoop => ("scoop", vec!["unhold", &package]),
+            _ => return None,
+        };
+
+        Some(
+            ShellCommand::new(cmd)
+                .with_args(args)
+                .with_sudo(self.needs_sudo(request)),
+        )
+    }
+
     pub fn install(
         &self,
         use_sudo: Option<bool>,
-        package: &str,
-        cask: Option<bool>,
+        request: &PackageRequest,
         dry_run: bool,
-    ) {
-        if matches!(self, Self::Nix) {
-            print_warn!(
-                "⚠️ It's recommended to install '{}' using Nix directly:\n    nix-env -iA nixpkgs.{}",
-                package,
-                package
-            );
-            return;
+    ) -> io::Result<()> {
+        if matches!(self, Self::Aur) {
+            return AurBuilder::new().install(request.name, dry_run);
         }
 
-        let (cmd, args) = match self {
-            Self::Apt | Self::Dnf | Self::Yum => (self.name(), vec!["install", package, "-y"]),
-            Self::Zypper => (self.name(), vec!["install", "-y", package]),
-            Self::Pacman | Self::Yay => (self.name(), vec!["-S", package, "--noconfirm"]),
-            Self::Portage => ("emerge", vec![package]),
-            Self::Apk => ("apk", vec!["add", package]),
-            Self::Flatpak => ("flatpak", vec!["install", "flathub", package]),
-            Self::Snap => ("snap", vec!["install", package]),
-            Self::Brew => {
-                let base = vec!["install"];
-                if cask.unwrap_or(false) {
-                    ("brew", [base.as_slice(), &["--cask", package]].concat())
-                } else {
-                    ("brew", [base.as_slice(), &[package]].concat())
-                }
-            }
-            Self::Winget => ("winget", vec!["install", package]),
-            Self::Choco => ("choco", vec!["install", package, "-y"]),
-            Self::Scoop => ("scoop", vec!["install", package]),
-            _ => return,
-        };
+        let sudo = use_sudo.unwrap_or(self.needs_sudo(request));
+        for cmd in self.prepare_commands(request) {
+            cmd.with_retry(Retry::package_manager())
+                .run_verbose(dry_run)?;
+        }
 
-        execute_package_cmd(
-            cmd,
-            &args,
-            use_sudo.unwrap_or(self.requires_sudo()),
-            dry_run,
-        );
+        // Moving a held package to another pin needs the old hold released
+        if let Some(wanted) = request.version
+            && self
+                .installed_version(request.name)
+                .is_some_and(|installed| !version_matches(&installed, wanted))
+            && let Some(unhold) = self.unhold_command(request)
+            && let Err(e) = unhold.with_sudo(sudo).run_verbose(dry_run)
+        {
+            print_warn!("Failed to release the hold on {}: {}", request.name, e);
+        }
+
+        self.install_command(request)?
+            .with_sudo(sudo)
+            .with_retry(Retry::package_manager())
+            .run_verbose(dry_run)
+    }
+
+    /// Holds a pinned package at its version. Installs call this after
+    /// [`Self::install`] and treat a failure as a warning only, since the
+    /// package itself is in place.
+    pub fn hold(
+        &self,
+        use_sudo: Option<bool>,
+        request: &PackageRequest,
+        dry_run: bool,
+    ) -> io::Result<()> {
+        match self.hold_command(request) {
+            Some(hold) => hold
+                .with_sudo(use_sudo.unwrap_or(self.needs_sudo(request)))
+                .run_verbose(dry_run),
+            None => Ok(()),
+        }
     }
 
     // pub fn update(&self, use_sudo: Option<bool>, dry_run: bool) {
@@ -190,36 +498,143 @@ impl PackageManager {
     //     execute_package_cmd(cmd, &args, use_sudo.unwrap_or(self.requires_sudo()), dry_run);
     // }
 
-    pub fn uninstall(&self, use_sudo: Option<bool>, package: &str, dry_run: bool) {
-        if matches!(self, Self::Nix) {
-            print_warn!(
-                "⚠️ To uninstall Nix packages, run:\n    nix-env -e {}",
-                package
-            );
-            return;
+    pub fn upgrade(
+        &self,
+        use_sudo: Option<bool>,
+        request: &PackageRequest,
+        dry_run: bool,
+    ) -> io::Result<()> {
+        // Pinned packages stay where they are
+        if request.version.is_some() {
+            print_warn!("Skipping upgrade of {}: version is pinned", request.name);
+            return Ok(());
+        }
+
+        self.upgrade_command(request)?
+            .with_sudo(use_sudo.unwrap_or(self.needs_sudo(request)))
+            .with_retry(Retry::package_manager())
+            .run_verbose(dry_run)
+    }
+
+    pub fn uninstall(
+        &self,
+        use_sudo: Option<bool>,
+        request: &PackageRequest,
+        dry_run: bool,
+    ) -> io::Result<()> {
+        let sudo = use_sudo.unwrap_or(self.needs_sudo(request));
+        // A held package can't be removed by some managers (brew, choco)
+        if let Some(unhold) = self.unhold_command(request) {
+            unhold.with_sudo(sudo).run_verbose(dry_run)?;
         }
 
+        self.uninstall_command(request)?
+            .with_sudo(sudo)
+            .with_retry(Retry::package_manager())
+            .run_verbose(dry_run)
+    }
+
+    /// Command that reports which of `packages` are installed, if the
+    /// manager supports querying several packages at once.
+    fn installed_query(&self, packages: &[&str]) -> Option<ShellCommand> {
+        use PackageManager::*;
+        let (cmd, mut args): (&str, Vec<&str>) = match self {
+            Apt => (
+                "dpkg-query",
+                vec!["-W", "-f=${Package} ${db:Status-Abbrev}\\n"],
+            ),
+            Dnf | Yum | Zypper => ("rpm", vec!["-q", "--qf", "%{NAME}\\n"]),
+            Pacman | Yay | Aur => ("pacman", vec!["-Q"]),
+            Apk => ("apk", vec!["info", "-e"]),
+            Portage => ("qlist", vec!["-I"]),
+            Xbps => ("xbps-query", vec!["-l"]),
+            Brew => ("brew", vec!["list", "-1"]),
+            Flatpak => ("flatpak", vec!["list", "--columns=application"]),
+            Snap => ("snap", vec!["list"]),
+            Choco => ("choco", vec!["list", "-r"]),
+            Scoop => ("scoop", vec!["list"]),
+            Nix if nix_profile_available() => return Some(nix_profile(["list", "--json"])),
+            Nix => ("nix-env", vec!["-q"]),
+            Winget => return None,
+        };
+
+        // Managers that list everything don't take package arguments
+        if matches!(self, Apt | Dnf | Yum | Zypper | Pacman | Yay | Aur | Apk) {
+            args.extend_from_slice(packages);
+        }
+        Some(ShellCommand::new(cmd).with_args(args))
+    }
+
+    /// Version of the installed `package`, for managers that can pin one.
+    ///
+    /// `None` when the package is missing or the manager can't report it.
+    pub fn installed_version(&self, package: &str) -> Option<String> {
+        let output = self.installed_version_query(package)?.execute().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        parse_installed_version(*self, &String::from_utf8_lossy(&output.stdout), package)
+    }
+
+    /// The query whose output [`parse_installed_version`] reads, for
+    /// managers that can pin a version.
+    pub fn installed_version_query(&self, package: &str) -> Option<ShellCommand> {
+        use PackageManager::*;
         let (cmd, args): (&str, Vec<&str>) = match self {
-            Self::Apt | Self::Dnf | Self::Yum => (self.name(), vec!["remove", package, "-y"]),
-            Self::Zypper => ("zypper", vec!["remove", "-y", package]),
-            Self::Pacman | Self::Yay => (self.name(), vec!["-R", package, "--noconfirm"]),
-            Self::Portage => ("emerge", vec!["-C", package]),
-            Self::Apk => ("apk", vec!["del", package]),
-            Self::Flatpak => ("flatpak", vec!["uninstall", "-y", package]),
-            Self::Snap => ("snap", vec!["remove", package]),
-            Self::Brew => ("brew", vec!["uninstall", package]),
-            Self::Choco => ("choco", vec!["uninstall", package, "-y"]),
-            Self::Winget => ("winget", vec!["uninstall", package]),
-            Self::Scoop => ("scoop", vec!["uninstall", package]),
-            _ => return,
+            Apt => ("dpkg-query", vec!["-W", "-f=${Version}", package]),
+            Dnf | Yum | Zypper => (
+                "rpm",
+                vec![
+                    "-q",
+                    "--qf",
+                    "%|EPOCH?{%{EPOCH}:}:{}|%{VERSION}-%{RELEASE}",
+                    package,
+                ],
+            ),
+            Apk => ("apk", vec!["list", "-I", package]),
+            Portage => ("qlist", vec!["-Iv", package]),
+            Choco => ("choco", vec!["list", "-r", "--exact", package]),
+            _ => return None,
         };
+        Some(ShellCommand::new(cmd).with_args(args))
+    }
 
-        execute_package_cmd(
-            cmd,
-            &args,
-            use_sudo.unwrap_or(self.requires_sudo()),
-            dry_run,
-        );
+    /// Returns the subset of `packages` that this manager reports as
+    /// installed, using a single query where possible.
+    ///
+    /// `None` means the manager is missing or cannot be queried.
+    pub fn installed_packages(&self, packages: &[&str]) -> Option<HashSet<String>> {
+        if !self.check_installed() {
+            return None;
+        }
+
+        if matches!(self, Self::Winget) {
+            let installed = packages
+                .iter()
+                .filter(|pkg| {
+                    ShellCommand::new("winget")
+                        .with_args(["list", "--exact", "--id", pkg])
+                        .succeeds()
+                })
+                .map(|pkg| pkg.to_string())
+                .collect();
+            return Some(installed);
+        }
+
+        // Most queries exit non-zero when some packages are missing, but
+        // still print the installed ones, so only stdout matters here.
+        let output = self.installed_query(packages)?.execute().ok()?;
+        Some(parse_installed(
+            *self,
+            &String::from_utf8_lossy(&output.stdout),
+            packages,
+        ))
+    }
+
+    /// Per-user flatpak installs are the only request that changes whether
+    /// the manager needs elevated privileges.
+    fn needs_sudo(&self, request: &PackageRequest) -> bool {
+        self.requires_sudo() && !(matches!(self, Self::Flatpak) && request.flatpak_user())
     }
 
     fn requires_sudo(&self) -> bool {
@@ -232,12 +647,217 @@ impl PackageManager {
                 | Self::Pacman
                 | Self::Portage
                 | Self::Snap
-                | Self::Yay
+                | Self::Xbps
                 | Self::Zypper
         )
     }
 }
 
+/// Extracts the installed packages out of `requested` from the output of
+/// the manager's installed-package query.
+pub fn parse_installed(pm: PackageManager, output: &str, requested: &[&str]) -> HashSet<String> {
+    use PackageManager::*;
+
+    let listed: Vec<&str> = match pm {
+        Apt => output
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                let name = parts.next()?;
+                parts.next()?.starts_with("ii").then_some(name)
+            })
+            .collect(),
+        // Header row: "Name  Version  Rev ..."
+        Snap | Scoop => output
+            .lines()
+            .skip_while(|line| !line.trim_start().starts_with("Name"))
+            .skip(1)
+            .filter_map(|line| line.split_whitespace().next())
+            .filter(|name| !name.starts_with('-'))
+            .collect(),
+        Choco => output
+            .lines()
+            .filter_map(|line| line.split('|').next())
+            .collect(),
+        Nix if output.trim_start().starts_with('{') => {
+            return nix_profile_installed(output, requested);
+        }
+        // nix-env -q prints `name-version`
+        Nix => output
+            .lines()
+            .map(|line| strip_version(line.trim()))
+            .collect(),
+        // xbps-query -l prints `ii name-version  description`
+        Xbps => output
+            .lines()
+            .filter_map(|line| line.split_whitespace().nth(1))
+            .map(strip_version)
+            .collect(),
+        _ => output
+            .lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .collect(),
+    };
+
+    let case_insensitive = matches!(pm, Choco | Scoop | Winget);
+    let same = |a: &str, b: &str| {
+        if case_insensitive {
+            a.eq_ignore_ascii_case(b)
+        } else {
+            a == b
+        }
+    };
+    // Brew taps (owner/tap/pkg) and Portage atoms (category/pkg) may be
+    // requested either fully qualified or by their short name.
+    let short = |name: &str| name.rsplit('/').next().unwrap_or(name).to_string();
+
+    requested
+        .iter()
+        .filter(|want| {
+            listed
+                .iter()
+                .any(|have| same(have, want) || same(&short(have), &short(want)))
+        })
+        .map(|pkg| pkg.to_string())
+        .collect()
+}
+
+/// Extracts the version of `package` from the output of the query used by
+/// [`PackageManager::installed_version`].
+pub fn parse_installed_version(pm: PackageManager, output: &str, package: &str) -> Option<String> {
+    use PackageManager::*;
+    let line = output.lines().map(str::trim).find(|l| !l.is_empty())?;
+    let short = package.rsplit('/').next().unwrap_or(package);
+
+    let version = match pm {
+        // `name-version arch {origin} (license) [installed]`
+        Apk => line
+            .split_whitespace()
+            .next()?
+            .strip_prefix(short)?
+            .strip_prefix('-')?,
+        // `category/name-version`
+        Portage => line
+            .rsplit('/')
+            .next()?
+            .strip_prefix(short)?
+            .strip_prefix('-')?,
+        // `name|version`
+        Choco => line.split_once('|')?.1,
+        _ => line,
+    };
+    Some(version.to_string())
+}
+
+/// Whether an installed version satisfies a pinned one. The pin may leave
+/// out the epoch or the package release, e.g. `27.3.1` matches
+/// `5:27.3.1-1~ubuntu`.
+pub fn version_matches(installed: &str, wanted: &str) -> bool {
+    let installed = if wanted.contains(':') {
+        installed
+    } else {
+        installed.split_once(':').map_or(installed, |(_, v)| v)
+    };
+    installed == wanted
+        || installed
+            .strip_prefix(wanted)
+            .is_some_and(|rest| rest.starts_with('-'))
+}
+
+/// Drops the version from a `name-version` package id; the version starts
+/// at the first dash followed by a digit.
+fn strip_version(pkgver: &str) -> &str {
+    pkgver
+        .match_indices('-')
+        .find(|(i, _)| pkgver[i + 1..].starts_with(|c: char| c.is_ascii_digit()))
+        .map_or(pkgver, |(i, _)| &pkgver[..i])
+}
+
+/// Matches `nix profile list --json` output, which keys elements by name
+/// in newer Nix and lists them with an `attrPath` in older releases.
+fn nix_profile_installed(output: &str, requested: &[&str]) -> HashSet<String> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(output) else {
+        return HashSet::new();
+    };
+
+    let names: Vec<String> = match json.get("elements") {
+        Some(serde_json::Value::Object(elements)) => elements.keys().cloned().collect(),
+        Some(serde_json::Value::Array(elements)) => elements
+            .iter()
+            .filter_map(|e| e.get("attrPath")?.as_str())
+            .filter_map(|path| path.rsplit('.').next())
+            .map(str::to_string)
+            .collect(),
+        _ => vec![],
+    };
+
+    requested
+        .iter()
+        .filter(|want| names.iter().any(|have| have == *want))
+        .map(|pkg| pkg.to_string())
+        .collect()
+}
+
+const NIX_FEATURES: [&str; 2] = ["--extra-experimental-features", "nix-command flakes"];
+
+fn nix_profile<'a>(args: impl IntoIterator<Item = &'a str>) -> ShellCommand {
+    ShellCommand::new("nix").with_args(NIX_FEATURES.into_iter().chain(["profile"]).chain(args))
+}
+
+/// `nix profile` needs a recent Nix and refuses profiles managed by
+/// `nix-env`, so older setups keep using `nix-env`.
+fn nix_profile_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| nix_profile(["list"]).succeeds())
+}
+
+/// `action` is one of `install`, `remove` or `upgrade`.
+fn nix_command(action: &str, package: &str) -> ShellCommand {
+    if nix_profile_available() {
+        let installable = format!("nixpkgs#{package}");
+        let target = if action == "install" {
+            installable.as_str()
+        } else {
+            package
+        };
+        return nix_profile([action, target]);
+    }
+
+    match action {
+        "install" => ShellCommand::new("nix-env").with_args(["-iA", &format!("nixpkgs.{package}")]),
+        "remove" => ShellCommand::new("nix-env").with_args(["-e", package]),
+        _ => ShellCommand::new("nix-env").with_args(["-u", package]),
+    }
+}
+
+/// Installed-package lookups for one run, queried once per manager.
+#[derive(Debug, Default)]
+pub struct InstalledPackages {
+    by_manager: HashMap<PackageManager, HashSet<String>>,
+}
+
+impl InstalledPackages {
+    /// Queries every manager once for all packages requested from it.
+    pub fn query<'a>(packages: impl IntoIterator<Item = (PackageManager, &'a str)>) -> Self {
+        let mut grouped: HashMap<PackageManager, Vec<&str>> = HashMap::new();
+        for (pm, pkg) in packages {
+            grouped.entry(pm).or_default().push(pkg);
+        }
+
+        let by_manager = grouped
+            .into_iter()
+            .filter_map(|(pm, pkgs)| Some((pm, pm.installed_packages(&pkgs)?)))
+            .collect();
+
+        Self { by_manager }
+    }
+
+    /// `None` when the manager was not queried or cannot be queried.
+    pub fn contains(&self, pm: PackageManager, package: &str) -> Option<bool> {
+        self.by_manager.get(&pm).map(|set| set.contains(package))
+    }
+}
+
 fn default_pm_installed(pm: PackageManager) -> bool {
     if command_exists(pm.name()) {
         true
@@ -256,64 +876,92 @@ fn default_pm_installed(pm: PackageManager) -> bool {
 
 // Installer functions
 fn install_choco() {
-    run_shell_command(
-        "Set-ExecutionPolicy Bypass -Scope Process -Force; iex ((New-Object System.Net.WebClient).DownloadString('https://chocolatey.org/install.ps1'))",
-    );
+    run_installer(&bootstrap::CHOCOLATEY, &[]);
 }
 
 fn install_scoop() {
-    run_shell_command("iwr -useb get.scoop.sh | iex");
+    run_installer(&bootstrap::SCOOP, &[]);
 }
 
 fn install_homebrew() {
-    run_shell_command(
-        "/bin/bash -c \"$(curl -fsSL https://raw.githubusercontent.com/Homebrew/install/HEAD/install.sh)\"",
-    );
+    run_installer(&bootstrap::HOMEBREW, &[]);
+}
+
+fn run_installer(installer: &Installer, args: &[&str]) {
+    if let Err(e) = installer.run(args, false) {
+        print_error!("❌ Failed to install {}: {}", installer.name, e);
+    }
 }
 
 fn install_yay() {
     if !check_command("yay", "Yay") {
         println!("Installing yay...");
-        run_shell_command("git clone https://aur.archlinux.org/yay.git");
-        run_shell_command("cd yay && makepkg -si --noconfirm");
-        run_shell_command("cd .. && rm -rf yay");
+        if let Err(e) = AurBuilder::new().with_helper(None).install("yay", false) {
+            print_error!("❌ Failed to install yay: {}", e);
+        }
     }
 }
 
-fn install_snap() {
-    let distro = SystemInfo::new().distro();
-    match distro.as_str() {
-        d if d.contains("Ubuntu") || d.contains("Debian") => {
-            run_shell_command("sudo apt update && sudo apt install snapd -y")
-        }
-        d if d.contains("Fedora") => run_shell_command("sudo dnf install snapd -y"),
-        d if d.contains("Arch") => run_shell_command("sudo pacman -S snapd -y"),
-        _ => print_error!("❌ Unsupported distribution: {}", distro),
+fn aur_available() -> bool {
+    if detect_helper().is_some() || aur::can_build() {
+        true
+    } else {
+        print_error!(
+            "❌ {}",
+            "AUR builds need git and makepkg: sudo pacman -S --needed git base-devel".red()
+        );
+        false
     }
 }
 
+fn install_snap() {
+    install_bootstrap_tool(BootstrapTool::Snap);
+}
+
 fn install_flatpak() {
-    let distro = SystemInfo::new().distro();
-    match distro.as_str() {
-        d if d.contains("Ubuntu") || d.contains("Debian") => {
-            run_shell_command("sudo apt update && sudo apt install flatpak -y")
-        }
-        d if d.contains("Fedora") => run_shell_command("sudo dnf install flatpak -y"),
-        d if d.contains("Arch") => run_shell_command("sudo pacman -S flatpak -y"),
-        _ => print_error!("❌ Unsupported distribution: {}", distro),
+    install_bootstrap_tool(BootstrapTool::Flatpak);
+}
+
+/// Installs `tool` with the distro's default manager, using the package
+/// name from the distro table.
+fn install_bootstrap_tool(tool: BootstrapTool) {
+    let Some(distro) = Distro::detect() else {
+        print_error!(
+            "❌ Unsupported distribution: {}",
+            SystemInfo::new().distro()
+        );
+        return;
+    };
+    let Some(package) = distro.family.bootstrap_package(tool) else {
+        print_error!(
+            "❌ {:?} is not packaged for {} systems",
+            tool,
+            distro.family
+        );
+        return;
+    };
+
+    let pm = distro.default_package_manager;
+    let result = match pm {
+        PackageManager::Apt => refresh_index(pm, false),
+        _ => Ok(()),
+    }
+    .and_then(|()| pm.install(None, &PackageRequest::new(package), false));
+
+    if let Err(e) = result {
+        print_error!("❌ Failed to install {}: {}", package, e);
     }
 }
 
 fn install_nix() {
-    let mut cmd =
-        "sh <(curl --proto '=https' --tlsv1.2 -L https://nixos.org/nix/install)".to_string();
+    let mut args = vec![];
     if SystemInfo::new().os_type() == OSType::Linux {
         let daemon = Confirm::new()
             .with_prompt("Do you want to install the Nix daemon?")
             .default(true)
             .interact()
             .unwrap();
-        cmd.push_str(if daemon { " --daemon" } else { " --no-daemon" });
+        args.push(if daemon { "--daemon" } else { "--no-daemon" });
     }
-    run_shell_command(&cmd);
+    run_installer(&bootstrap::NIX, &args);
 }
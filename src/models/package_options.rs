@@ -0,0 +1,58 @@
+// Module: Model/PackageOptions
+// Location: cli/src/model/package_options.rs
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Manager-specific settings for an install method.
+///
+/// Only the block matching the method's `package_manager` may be set.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PackageOptions {
+    #[serde(default)]
+    pub apt: Option<AptOptions>,
+    #[serde(default)]
+    pub brew: Option<BrewOptions>,
+    #[serde(default)]
+    pub flatpak: Option<FlatpakOptions>,
+    #[serde(default)]
+    pub snap: Option<SnapOptions>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AptOptions {
+    /// Pass `--no-install-recommends`
+    #[serde(default)]
+    pub no_install_recommends: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BrewOptions {
+    /// Third-party tap to add before installing, e.g. `hashicorp/tap`
+    #[serde(default)]
+    pub tap: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FlatpakOptions {
+    /// Remote to install from, defaults to `flathub`
+    #[serde(default)]
+    pub remote: Option<String>,
+    /// Install for the current user instead of system-wide
+    #[serde(default)]
+    pub user: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SnapOptions {
+    /// Install with classic confinement
+    #[serde(default)]
+    pub classic: bool,
+    /// Channel to track, e.g. `latest/stable` or `1.30/stable`
+    #[serde(default)]
+    pub channel: Option<String>,
+}
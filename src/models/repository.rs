@@ -0,0 +1,333 @@
+// Module: Model/Repository
+// Location: cli/src/model/repository.rs
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+
+use crate::{
+    core::{
+        retry::{self, Retry},
+        shell::ShellCommand,
+        template::TemplateContext,
+    },
+    models::{package_manager::PackageManager, system::os_release},
+    print_info,
+};
+
+const APT_KEYRINGS: &str = "/etc/apt/keyrings";
+const APT_SOURCES: &str = "/etc/apt/sources.list.d";
+const DNF_REPOS: &str = "/etc/yum.repos.d";
+
+/// A vendor repository an install method needs before its package resolves.
+///
+/// apt repositories use `url`, `suite` and `components`; dnf repositories
+/// use a `.repo` file URL, a `url` to generate one from, or a COPR project.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Repository {
+    /// File name used for the keyring and source/repo files, e.g. `docker`
+    pub name: String,
+    /// URL of the signing key
+    #[serde(default)]
+    pub key_url: Option<String>,
+    /// Expected fingerprint of the signing key; the key is rejected on mismatch
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    /// apt: repository URL. dnf: `baseurl` of the generated `.repo` file
+    #[serde(default)]
+    pub url: Option<String>,
+    /// apt: suite, defaults to the release codename from /etc/os-release
+    #[serde(default)]
+    pub suite: Option<String>,
+    /// apt: components, e.g. `["stable"]` or `["main"]`
+    #[serde(default)]
+    pub components: Vec<String>,
+    /// dnf: URL of a ready-made `.repo` file
+    #[serde(default)]
+    pub repo_file: Option<String>,
+    /// dnf: COPR project as `owner/project`
+    #[serde(default)]
+    pub copr: Option<String>,
+}
+
+impl Repository {
+    pub fn keyring_path(&self) -> PathBuf {
+        Path::new(APT_KEYRINGS).join(format!("{}.gpg", self.name))
+    }
+
+    pub fn apt_source_path(&self) -> PathBuf {
+        Path::new(APT_SOURCES).join(format!("{}.list", self.name))
+    }
+
+    pub fn dnf_repo_path(&self) -> PathBuf {
+        Path::new(DNF_REPOS).join(format!("{}.repo", self.name))
+    }
+
+    /// The `deb` line written to the apt sources list.
+    pub fn apt_source(&self, ctx: &TemplateContext) -> io::Result<String> {
+        let url = self.url.as_deref().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("apt repository '{}' has no url", self.name),
+            )
+        })?;
+        let suite = match &self.suite {
+            Some(suite) => render(ctx, suite)?,
+            None => os_release().remove("VERSION_CODENAME").ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "no suite given and VERSION_CODENAME missing from /etc/os-release",
+                )
+            })?,
+        };
+
+        let mut options = vec![format!("arch={}", debian_arch(ctx.get("arch")))];
+        if self.key_url.is_some() {
+            options.push(format!("signed-by={}", self.keyring_path().display()));
+        }
+
+        Ok(format!(
+            "deb [{}] {} {} {}\n",
+            options.join(" "),
+            render(ctx, url)?,
+            suite,
+            self.components.join(" ")
+        ))
+    }
+
+    /// The `.repo` file generated when only a `url` is given.
+    pub fn dnf_repo(&self, ctx: &TemplateContext) -> io::Result<String> {
+        let url = self.url.as_deref().unwrap_or_default();
+        let mut repo = format!(
+            "[{name}]\nname={name}\nbaseurl={url}\nenabled=1\n",
+            name = self.name,
+            url = render(ctx, url)?
+        );
+        match &self.key_url {
+            Some(key) => repo.push_str(&format!("gpgcheck=1\ngpgkey={}\n", render(ctx, key)?)),
+            None => repo.push_str("gpgcheck=0\n"),
+        }
+        Ok(repo)
+    }
+
+    /// Adds the repository and its signing key. The package index is not
+    /// refreshed; call [`refresh_index`] once after adding a batch.
+    pub fn add(&self, pm: PackageManager, ctx: &TemplateContext, dry_run: bool) -> io::Result<()> {
+        print_info!("📚 Adding {} repository {}", pm.name(), self.name);
+        match pm {
+            PackageManager::Apt => {
+                if let Some(ref key_url) = self.key_url {
+                    let key = self.fetch_key(&render(ctx, key_url)?, dry_run)?;
+                    install_key(&key, &self.keyring_path(), dry_run)?;
+                }
+                install_file(
+                    self.apt_source(ctx)?.as_bytes(),
+                    &self.apt_source_path(),
+                    dry_run,
+                )
+            }
+            PackageManager::Dnf | PackageManager::Yum => {
+                if let Some(ref copr) = self.copr {
+                    return ShellCommand::new(pm.name())
+                        .with_args(["copr", "enable", "-y", copr])
+                        .with_sudo(true)
+                        .run_verbose(dry_run);
+                }
+
+                if let Some(ref key_url) = self.key_url {
+                    let key = self.fetch_key(&render(ctx, key_url)?, dry_run)?;
+                    if let Some(key) = key {
+                        ShellCommand::new("rpm")
+                            .with_args(["--import".into(), key.path().display().to_string()])
+                            .with_sudo(true)
+                            .run_verbose(dry_run)?;
+                    }
+                }
+
+                let contents = match &self.repo_file {
+                    Some(url) => download(&render(ctx, url)?, dry_run)?,
+                    None => self.dnf_repo(ctx)?.into_bytes(),
+                };
+                install_file(&contents, &self.dnf_repo_path(), dry_run)
+            }
+            other => Err(unsupported(other)),
+        }
+    }
+
+    /// Removes everything [`Repository::add`] created.
+    pub fn remove(&self, pm: PackageManager, dry_run: bool) -> io::Result<()> {
+        print_info!("🧹 Removing {} repository {}", pm.name(), self.name);
+        let cmd = match pm {
+            PackageManager::Apt => ShellCommand::new("rm").with_args([
+                "-f".into(),
+                self.apt_source_path().display().to_string(),
+                self.keyring_path().display().to_string(),
+            ]),
+            PackageManager::Dnf | PackageManager::Yum => match &self.copr {
+                Some(copr) => {
+                    ShellCommand::new(pm.name()).with_args(["copr", "remove", "-y", copr])
+                }
+                None => ShellCommand::new("rm")
+                    .with_args(["-f".into(), self.dnf_repo_path().display().to_string()]),
+            },
+            other => return Err(unsupported(other)),
+        };
+        cmd.with_sudo(true).run_verbose(dry_run)
+    }
+
+    /// Downloads the signing key and checks its fingerprint.
+    ///
+    /// Returns `None` in dry-run mode, where nothing is downloaded.
+    fn fetch_key(&self, url: &str, dry_run: bool) -> io::Result<Option<NamedTempFile>> {
+        if dry_run {
+            print_info!("Would download signing key {}", url);
+            return Ok(None);
+        }
+
+        let mut key = NamedTempFile::new()?;
+        key.write_all(&download(url, false)?)?;
+
+        if let Some(ref expected) = self.fingerprint {
+            let output = ShellCommand::new("gpg")
+                .with_args([
+                    "--show-keys".into(),
+                    "--with-colons".into(),
+                    key.path().display().to_string(),
+                ])
+                .execute()?;
+            let found = parse_fingerprints(&String::from_utf8_lossy(&output.stdout));
+            if !found.iter().any(|fpr| same_fingerprint(fpr, expected)) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "signing key for '{}' does not match fingerprint {} (found: {})",
+                        self.name,
+                        expected,
+                        found.join(", ")
+                    ),
+                ));
+            }
+        }
+
+        Ok(Some(key))
+    }
+}
+
+/// Refreshes the package index after repositories were added.
+pub fn refresh_index(pm: PackageManager, dry_run: bool) -> io::Result<()> {
+    refresh_command(pm)?
+        .with_retry(Retry::package_manager())
+        .run_verbose(dry_run)
+}
+
+/// The command that refreshes `pm`'s package index.
+pub fn refresh_command(pm: PackageManager) -> io::Result<ShellCommand> {
+    let cmd = match pm {
+        PackageManager::Apt => ShellCommand::new("apt-get").with_args(["update"]),
+        PackageManager::Dnf | PackageManager::Yum => {
+            ShellCommand::new(pm.name()).with_args(["makecache"])
+        }
+        other => return Err(unsupported(other)),
+    };
+    Ok(cmd.with_sudo(true))
+}
+
+/// Extracts key fingerprints from `gpg --with-colons` output.
+pub fn parse_fingerprints(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter(|line| line.starts_with("fpr:"))
+        .filter_map(|line| line.split(':').nth(9))
+        .filter(|fpr| !fpr.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Compares fingerprints ignoring case and the spaces vendors print them with.
+pub fn same_fingerprint(a: &str, b: &str) -> bool {
+    let normalize = |s: &str| {
+        s.chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_uppercase()
+    };
+    normalize(a) == normalize(b)
+}
+
+/// Writes an armored key dearmored, and a binary key as-is.
+fn install_key(key: &Option<NamedTempFile>, dest: &Path, dry_run: bool) -> io::Result<()> {
+    let src = key
+        .as_ref()
+        .map(|k| k.path().display().to_string())
+        .unwrap_or_else(|| "<downloaded key>".into());
+    let armored = match key {
+        Some(k) => std::fs::read(k.path())?.starts_with(b"-----BEGIN PGP"),
+        None => true,
+    };
+
+    ShellCommand::new("install")
+        .with_args(["-d", "-m", "0755", APT_KEYRINGS])
+        .with_sudo(true)
+        .run_verbose(dry_run)?;
+
+    let dest = dest.display().to_string();
+    let cmd = if armored {
+        ShellCommand::new("gpg").with_args(["--batch", "--yes", "--dearmor", "-o", &dest, &src])
+    } else {
+        ShellCommand::new("install").with_args(["-m", "0644", &src, &dest])
+    };
+    cmd.with_sudo(true).run_verbose(dry_run)
+}
+
+/// Writes `contents` to a root-owned file through a temp file.
+fn install_file(contents: &[u8], dest: &Path, dry_run: bool) -> io::Result<()> {
+    let mut tmp = NamedTempFile::new()?;
+    tmp.write_all(contents)?;
+
+    ShellCommand::new("install")
+        .with_args([
+            "-D".into(),
+            "-m".into(),
+            "0644".into(),
+            tmp.path().display().to_string(),
+            dest.display().to_string(),
+        ])
+        .with_sudo(true)
+        .run_verbose(dry_run)
+}
+
+fn download(url: &str, dry_run: bool) -> io::Result<Vec<u8>> {
+    if dry_run {
+        print_info!("Would download {}", url);
+        return Ok(vec![]);
+    }
+    retry::download(url)
+}
+
+fn render(ctx: &TemplateContext, input: &str) -> io::Result<String> {
+    ctx.render(input)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+/// Debian names architectures differently from Rust.
+fn debian_arch(arch: Option<&str>) -> &str {
+    match arch.unwrap_or(std::env::consts::ARCH) {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        "x86" => "i386",
+        "arm" => "armhf",
+        other => other,
+    }
+}
+
+fn unsupported(pm: PackageManager) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("{} repositories are not supported", pm.name()),
+    )
+}
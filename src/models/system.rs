@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashMap, fs};
 
 use bitflags::bitflags;
 use colored::Colorize;
@@ -7,7 +7,7 @@ use schemars::{JsonSchema, Schema, SchemaGenerator};
 use serde::{Deserialize, Serialize};
 use sysinfo::System;
 
-use crate::{core::shell::command::command_exists, models::package_manager::PackageManager};
+use crate::models::{distro::Distro, package_manager::PackageManager};
 
 #[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub struct OsTypeWrapper {
@@ -68,6 +68,7 @@ pub struct SystemInfo {
     raw_os: os_info::Type,
     arch: String,
     distro: Option<String>,
+    distro_info: Option<&'static Distro>,
     cpu_vendor: Option<String>,
     cpu_brand: Option<String>,
     default_package_manager: Option<PackageManager>,
@@ -87,6 +88,7 @@ impl SystemInfo {
         let os = normalized_os_type(&info);
         let arch = std::env::consts::ARCH.to_owned();
         let distro = Some(info.os_type().to_string());
+        let distro_info = Distro::detect();
 
         let sys = System::new_all();
         let cpu = sys.cpus().first();
@@ -94,14 +96,20 @@ impl SystemInfo {
         let cpu_brand = cpu.map(|c| c.brand().to_owned());
         let cpu_vendor = cpu.map(|c| c.vendor_id().to_owned());
 
-        let default_package_manager = detect_default_package_manager(&info);
-        let available_package_managers = detect_available_package_managers(&os);
+        let supported = supported_package_managers(raw_os, distro_info);
+        let available_package_managers: Vec<_> = supported
+            .into_iter()
+            .filter(|pm| pm.check_installed()) // Non-interactive version (no prompting)
+            .collect();
+        let default_package_manager =
+            detect_default_package_manager(&info, distro_info, &available_package_managers);
 
         SystemInfo {
             os,
             raw_os,
             arch,
             distro,
+            distro_info,
             cpu_vendor,
             cpu_brand,
             default_package_manager,
@@ -117,10 +125,24 @@ impl SystemInfo {
         self.raw_os
     }
 
+    pub fn arch(&self) -> &str {
+        &self.arch
+    }
+
     pub fn distro(&self) -> String {
         self.distro.as_deref().unwrap_or("Unknown").to_string()
     }
 
+    /// Distro table entry for the running Linux system.
+    pub fn distro_info(&self) -> Option<&'static Distro> {
+        self.distro_info
+    }
+
+    /// Managers usable on this system, whether installed or not.
+    pub fn supported_package_managers(&self) -> Vec<PackageManager> {
+        supported_package_managers(self.raw_os, self.distro_info)
+    }
+
     pub fn cpu_brand(&self) -> String {
         self.cpu_brand.as_deref().unwrap_or("Unknown").to_string()
     }
@@ -178,13 +200,42 @@ impl SystemInfo {
 
     pub fn install_additional_pms(&self) {
         // Optional: Trigger installation logic on-demand (could be called manually)
-        let _installed = PackageManager::supported_on_os(self.raw_os)
+        let _installed = self
+            .supported_package_managers()
             .into_iter()
             .filter(|pm| pm.check_install())
             .collect::<Vec<_>>();
     }
 }
 
+/// Parses `KEY=value` pairs from an os-release file, unquoting values.
+pub fn parse_os_release(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                .unwrap_or(value);
+            (key.trim().to_string(), value.to_string())
+        })
+        .collect()
+}
+
+/// The local `/etc/os-release`, falling back to `/usr/lib/os-release`.
+pub fn os_release() -> HashMap<String, String> {
+    ["/etc/os-release", "/usr/lib/os-release"]
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .map(|content| parse_os_release(&content))
+        .unwrap_or_default()
+}
+
 #[derive(Copy, Clone, Debug, clap::ValueEnum, Deserialize, Serialize, JsonSchema)]
 pub enum SystemSupport {
     Cross,
@@ -228,45 +279,45 @@ pub enum ServerSupport {
 }
 
 fn normalized_os_type(info: &os_info::Info) -> OSType {
-    match info.os_type() {
-        OSType::Ubuntu
-        | OSType::Debian
-        | OSType::Fedora
-        | OSType::Redhat
-        | OSType::Alpine
-        | OSType::Arch
-        | OSType::Linux
-        | OSType::Pop
-        | OSType::EndeavourOS
-        | OSType::Manjaro => OSType::Linux,
-        other => other, // includes Windows, MacOS, BSD, etc.
+    // Every distribution is treated as Linux; the distro table tells them apart
+    if cfg!(target_os = "linux") {
+        OSType::Linux
+    } else {
+        info.os_type() // includes Windows, MacOS, BSD, etc.
     }
 }
 
-fn detect_default_package_manager(info: &os_info::Info) -> Option<PackageManager> {
-    use PackageManager::*;
-
-    match info.os_type() {
-        OSType::Ubuntu | OSType::Debian => Some(Apt),
-        OSType::Fedora => Some(Dnf),
-        OSType::Redhat => Some(Yum),
-        OSType::Alpine => Some(Apk),
-        OSType::Arch | OSType::Manjaro | OSType::EndeavourOS => Some(Pacman),
-        OSType::Macos => Some(Brew),
-        OSType::Windows => Some(Winget),
-        _ => {
-            // Fallback: first one found
-            [Apt, Dnf, Yum, Apk, Pacman, Brew, Winget, Scoop, Choco]
-                .iter()
-                .copied()
-                .find(|pm| command_exists(pm.name()))
-        }
+fn supported_package_managers(os: OSType, distro: Option<&Distro>) -> Vec<PackageManager> {
+    match distro {
+        Some(distro) => distro.package_managers.to_vec(),
+        None => PackageManager::supported_on_os(os),
     }
 }
 
-fn detect_available_package_managers(os: &OSType) -> Vec<PackageManager> {
-    PackageManager::supported_on_os(*os)
-        .into_iter()
-        .filter(|pm| pm.check_installed()) // Non-interactive version (no prompting)
-        .collect()
+fn detect_default_package_manager(
+    info: &os_info::Info,
+    distro: Option<&Distro>,
+    available: &[PackageManager],
+) -> Option<PackageManager> {
+    use PackageManager::*;
+
+    let preferred = match (distro, info.os_type()) {
+        (Some(distro), _) => Some(distro.default_package_manager),
+        (None, OSType::Macos) => Some(Brew),
+        (None, OSType::Windows) => Some(Winget),
+        _ => None,
+    };
+
+    preferred
+        .filter(|pm| available.is_empty() || available.contains(pm))
+        .or_else(|| available.first().copied())
+        .or_else(|| {
+            // Fallback: first one found
+            [
+                Apt, Dnf, Yum, Zypper, Apk, Pacman, Xbps, Brew, Winget, Scoop, Choco,
+            ]
+            .iter()
+            .copied()
+            .find(|pm| pm.check_installed())
+        })
 }
@@ -0,0 +1,358 @@
+// Module: Model/VPS/Facts
+// Location: cli/src/model/vps/facts.rs
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::Utc;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::shell::{ShellCommand, output::Stream},
+    models::{
+        distro::Distro,
+        package_manager::PackageManager,
+        system::parse_os_release,
+        vps::{json::VpsEntry, ssh},
+    },
+};
+
+/// How long gathering may take once connected, jump hosts included.
+const GATHER_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Prints each fact under a `### section` header. Only POSIX tools and
+/// `/proc` are used, and missing ones leave their section empty.
+pub const FACTS_SCRIPT: &str = r#"echo '### os-release'
+cat /etc/os-release 2>/dev/null || cat /usr/lib/os-release 2>/dev/null
+echo '### kernel'
+uname -sr
+echo '### arch'
+uname -m
+echo '### uptime'
+cat /proc/uptime 2>/dev/null
+echo '### cpu'
+nproc 2>/dev/null
+grep -m1 'model name' /proc/cpuinfo 2>/dev/null
+echo '### memory'
+grep -E '^(MemTotal|MemAvailable):' /proc/meminfo 2>/dev/null
+echo '### disk'
+df -P -k 2>/dev/null
+echo '### package-managers'
+for pm in apt dnf yum zypper apk pacman xbps-install emerge snap flatpak nix brew; do
+  command -v "$pm" >/dev/null 2>&1 && echo "$pm"
+done
+true
+"#;
+
+/// Usage of one mounted filesystem, in KiB.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskUsage {
+    pub filesystem: String,
+    pub mount: String,
+    pub total_kb: u64,
+    pub used_kb: u64,
+    pub available_kb: u64,
+}
+
+/// What a VPS runs, gathered over SSH and cached per entry.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RemoteFacts {
+    pub vps_id: String,
+    pub collected_at: String,
+    /// os-release `PRETTY_NAME`, e.g. `Ubuntu 24.04 LTS`
+    pub os: Option<String>,
+    /// os-release `ID`
+    pub distro: Option<String>,
+    /// os-release `ID_LIKE`
+    #[serde(default)]
+    pub distro_like: Vec<String>,
+    pub version: Option<String>,
+    pub kernel: Option<String>,
+    pub arch: Option<String>,
+    pub uptime_secs: Option<u64>,
+    pub cpu_count: Option<u32>,
+    pub cpu_brand: Option<String>,
+    pub memory_total_kb: Option<u64>,
+    pub memory_available_kb: Option<u64>,
+    #[serde(default)]
+    pub disks: Vec<DiskUsage>,
+    pub default_package_manager: Option<PackageManager>,
+    #[serde(default)]
+    pub available_package_managers: Vec<PackageManager>,
+}
+
+/// Pseudo filesystems `df` lists that say nothing about disk space.
+const PSEUDO_FILESYSTEMS: &[&str] = &["tmpfs", "devtmpfs", "overlay", "udev", "none", "shm"];
+
+impl RemoteFacts {
+    /// Parses the output of [`FACTS_SCRIPT`].
+    pub fn parse(vps_id: &str, output: &str) -> Self {
+        let mut facts = RemoteFacts {
+            vps_id: vps_id.to_string(),
+            collected_at: Utc::now().to_rfc3339(),
+            ..Self::default()
+        };
+
+        for (section, body) in sections(output) {
+            let body = body.as_str();
+            let mut lines = body.lines().map(str::trim).filter(|l| !l.is_empty());
+            match section {
+                "os-release" => {
+                    let release = parse_os_release(body);
+                    facts.os = release.get("PRETTY_NAME").cloned();
+                    facts.distro = release.get("ID").cloned();
+                    facts.distro_like = release
+                        .get("ID_LIKE")
+                        .map(|like| like.split_whitespace().map(String::from).collect())
+                        .unwrap_or_default();
+                    facts.version = release.get("VERSION_ID").cloned();
+                }
+                "kernel" => facts.kernel = lines.next().map(String::from),
+                "arch" => facts.arch = lines.next().map(String::from),
+                "uptime" => {
+                    facts.uptime_secs = lines
+                        .next()
+                        .and_then(|l| l.split_whitespace().next())
+                        .and_then(|secs| secs.parse::<f64>().ok())
+                        .map(|secs| secs as u64)
+                }
+                "cpu" => {
+                    for line in lines {
+                        if let Ok(count) = line.parse() {
+                            facts.cpu_count = Some(count);
+                        } else if let Some((_, brand)) = line.split_once(':') {
+                            facts.cpu_brand = Some(brand.trim().to_string());
+                        }
+                    }
+                }
+                "memory" => {
+                    for line in lines {
+                        let mut fields = line.split_whitespace();
+                        let key = fields.next();
+                        let kb = fields.next().and_then(|v| v.parse().ok());
+                        match key {
+                            Some("MemTotal:") => facts.memory_total_kb = kb,
+                            Some("MemAvailable:") => facts.memory_available_kb = kb,
+                            _ => {}
+                        }
+                    }
+                }
+                "disk" => facts.disks = lines.skip(1).filter_map(parse_df_line).collect(),
+                "package-managers" => {
+                    facts.available_package_managers =
+                        lines.filter_map(package_manager_for).collect()
+                }
+                _ => {}
+            }
+        }
+
+        facts.default_package_manager = facts
+            .distro_info()
+            .map(|d| d.default_package_manager)
+            .filter(|pm| facts.available_package_managers.contains(pm))
+            .or_else(|| facts.available_package_managers.first().copied());
+        facts
+    }
+
+    /// Distro table entry for the remote system, like
+    /// [`SystemInfo::distro_info`](crate::models::system::SystemInfo::distro_info).
+    pub fn distro_info(&self) -> Option<&'static Distro> {
+        let like: Vec<&str> = self.distro_like.iter().map(String::as_str).collect();
+        Distro::lookup(self.distro.as_deref()?, &like)
+    }
+
+    pub fn path(dir: &Path, vps_id: &str) -> PathBuf {
+        dir.join(format!(
+            "{}.json",
+            vps_id.replace(|c: char| !c.is_alphanumeric() && c != '-', "_")
+        ))
+    }
+
+    pub fn save(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(Self::path(dir, &self.vps_id), json)
+    }
+
+    /// The cached facts for `vps_id`, if any were gathered.
+    pub fn load(dir: &Path, vps_id: &str) -> Option<Self> {
+        let content = fs::read_to_string(Self::path(dir, vps_id)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn to_pretty_string(&self) -> String {
+        let unknown = || "Unknown".to_string();
+        let memory = match (self.memory_available_kb, self.memory_total_kb) {
+            (Some(free), Some(total)) => {
+                format!("{} free of {}", format_kb(free), format_kb(total))
+            }
+            (None, Some(total)) => format_kb(total),
+            _ => unknown(),
+        };
+        let disks = self
+            .disks
+            .iter()
+            .map(|d| {
+                format!(
+                    "\n     {} {} used of {} ({} free)",
+                    d.mount,
+                    format_kb(d.used_kb),
+                    format_kb(d.total_kb),
+                    format_kb(d.available_kb)
+                )
+            })
+            .collect::<String>();
+        let managers = if self.available_package_managers.is_empty() {
+            "None".to_string()
+        } else {
+            self.available_package_managers
+                .iter()
+                .map(|pm| pm.name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        format!(
+            "\n🧠 {} {}\n\
+             🐧  {} {}\n\
+             🧱  {} {}\n\
+             ⚙️  {} {}\n\
+             ⏱  {} {}\n\
+             🧬  {} {}\n\
+             💾  {} {}\n\
+             🗄  {} {}\n\
+             📦  {} {}\n\
+             📦  {} {}\n\
+             🕒  {} {}\n",
+            "Remote Facts".bold().underline().cyan(),
+            self.vps_id.bold(),
+            "OS:".bold().green(),
+            self.os.clone().unwrap_or_else(unknown),
+            "Arch:".bold().green(),
+            self.arch.clone().unwrap_or_else(unknown),
+            "Kernel:".bold().green(),
+            self.kernel.clone().unwrap_or_else(unknown),
+            "Uptime:".bold().green(),
+            self.uptime_secs.map(format_uptime).unwrap_or_else(unknown),
+            "CPU:".bold().green(),
+            match (self.cpu_count, &self.cpu_brand) {
+                (Some(count), Some(brand)) => format!("{count} × {brand}"),
+                (Some(count), None) => format!("{count} cores"),
+                (None, Some(brand)) => brand.clone(),
+                (None, None) => unknown(),
+            },
+            "Memory:".bold().green(),
+            memory,
+            "Disks:".bold().green(),
+            if disks.is_empty() { unknown() } else { disks },
+            "Default Package Manager:".bold().green(),
+            self.default_package_manager
+                .map(|pm| pm.name().to_string())
+                .unwrap_or_else(unknown),
+            "Available Package Managers:".bold().green(),
+            managers,
+            "Collected:".bold().green(),
+            self.collected_at,
+        )
+    }
+}
+
+/// Splits script output into `(section, body)` pairs at `### ` lines.
+fn sections(output: &str) -> Vec<(&str, String)> {
+    let mut sections: Vec<(&str, String)> = Vec::new();
+    for line in output.lines() {
+        if let Some(name) = line.strip_prefix("### ") {
+            sections.push((name.trim(), String::new()));
+        } else if let Some((_, body)) = sections.last_mut() {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    sections
+}
+
+/// A `df -P -k` row; pseudo filesystems are skipped. Mount points may
+/// contain spaces, so everything after the capacity column is the mount.
+fn parse_df_line(line: &str) -> Option<DiskUsage> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 6 || PSEUDO_FILESYSTEMS.contains(&fields[0]) {
+        return None;
+    }
+    Some(DiskUsage {
+        filesystem: fields[0].to_string(),
+        total_kb: fields[1].parse().ok()?,
+        used_kb: fields[2].parse().ok()?,
+        available_kb: fields[3].parse().ok()?,
+        mount: fields[5..].join(" "),
+    })
+}
+
+fn package_manager_for(command: &str) -> Option<PackageManager> {
+    use PackageManager::*;
+    Some(match command {
+        "apt" => Apt,
+        "dnf" => Dnf,
+        "yum" => Yum,
+        "zypper" => Zypper,
+        "apk" => Apk,
+        "pacman" => Pacman,
+        "xbps-install" => Xbps,
+        "emerge" => Portage,
+        "snap" => Snap,
+        "flatpak" => Flatpak,
+        "nix" => Nix,
+        "brew" => Brew,
+        _ => return None,
+    })
+}
+
+fn format_kb(kb: u64) -> String {
+    const UNITS: &[&str] = &["KiB", "MiB", "GiB", "TiB"];
+    let mut value = kb as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+fn format_uptime(secs: u64) -> String {
+    let days = secs / 86_400;
+    let hours = secs % 86_400 / 3600;
+    let minutes = secs % 3600 / 60;
+    if days > 0 {
+        format!("{days}d {hours}h {minutes}m")
+    } else {
+        format!("{hours}h {minutes}m")
+    }
+}
+
+/// Runs [`FACTS_SCRIPT`] on `vps` and parses what it printed.
+///
+/// ssh runs in batch mode, so a host asking for a password fails instead
+/// of blocking the other hosts gathered alongside it.
+pub fn gather(vps: &VpsEntry, inventory: &[VpsEntry]) -> io::Result<RemoteFacts> {
+    let mut args = ssh::batch_args(ssh::CONNECT_TIMEOUT);
+    args.extend(ssh::ssh_args(vps, inventory)?);
+    let (status, output) =
+        ShellCommand::from_remote_script(&ssh::destination(vps), &args, FACTS_SCRIPT, false)
+            .with_timeout(Some(GATHER_TIMEOUT))
+            .capture()?;
+
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "ssh exited with status code {}: {}",
+            status.code().unwrap_or(-1),
+            output.text(Stream::Stderr).trim()
+        )));
+    }
+    Ok(RemoteFacts::parse(
+        vps.effective_id(),
+        &output.text(Stream::Stdout),
+    ))
+}
@@ -0,0 +1,198 @@
+// Module: Model/VPS/Fleet
+// Location: cli/src/model/vps/fleet.rs
+use std::{
+    io,
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use colored::Colorize;
+use tabled::{Table, Tabled, settings::Style};
+
+use crate::{
+    core::shell::{ShellCommand, output},
+    models::vps::{json::VpsEntry, ssh},
+};
+
+/// Which entries a fleet command runs on: every entry with `all`, else
+/// those whose id is in `ids` or that carry one of `tags`.
+#[derive(Debug, Clone, Default)]
+pub struct Selector {
+    pub ids: Vec<String>,
+    pub tags: Vec<String>,
+    pub all: bool,
+}
+
+impl Selector {
+    /// Whether the selector names more than a single `--id`.
+    pub fn is_fleet(&self) -> bool {
+        self.all || !self.tags.is_empty() || self.ids.len() > 1
+    }
+
+    /// The matching entries, in config order. Fails on an unknown id or
+    /// when nothing matches.
+    pub fn select<'a>(&self, entries: &'a [VpsEntry]) -> io::Result<Vec<&'a VpsEntry>> {
+        if let Some(id) = self
+            .ids
+            .iter()
+            .find(|id| !entries.iter().any(|v| v.id.as_ref() == Some(*id)))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no VPS found with ID '{id}'"),
+            ));
+        }
+
+        let selected: Vec<&VpsEntry> = entries
+            .iter()
+            .filter(|v| {
+                self.all
+                    || v.id.as_ref().is_some_and(|id| self.ids.contains(id))
+                    || v.tags.iter().any(|tag| self.tags.contains(tag))
+            })
+            .collect();
+
+        if selected.is_empty() {
+            let message = if self.tags.is_empty() {
+                "no VPS entries found".to_string()
+            } else {
+                format!("no VPS is tagged {}", self.tags.join(" or "))
+            };
+            return Err(io::Error::new(io::ErrorKind::NotFound, message));
+        }
+        Ok(selected)
+    }
+}
+
+/// Calls `f` on every item from at most `limit` threads at once, returning
+/// the results in the order of `items`.
+pub fn run_parallel<T, R, F>(items: &[T], limit: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..limit.clamp(1, items.len().max(1)) {
+            scope.spawn(|| {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(i) else { break };
+                    let result = f(item);
+                    results.lock().unwrap()[i] = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.expect("every item is processed"))
+        .collect()
+}
+
+/// How a command went on one host.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostResult {
+    pub id: String,
+    /// Exit code, `None` when the command couldn't run or was killed
+    pub code: Option<i32>,
+    pub duration: Duration,
+    pub error: Option<String>,
+}
+
+impl HostResult {
+    pub fn success(&self) -> bool {
+        self.code == Some(0) && self.error.is_none()
+    }
+}
+
+/// Runs `script` on `vps` over SSH, with every output line prefixed by the
+/// entry's id. In quiet mode the output is only printed if the script fails.
+///
+/// ssh runs in batch mode so a host asking for a password fails instead of
+/// stalling the fleet, and the script is killed after `timeout`.
+pub fn run_script_on(
+    vps: &VpsEntry,
+    inventory: &[VpsEntry],
+    script: &str,
+    sudo: bool,
+    timeout: Option<Duration>,
+) -> HostResult {
+    let id = vps.effective_id().to_string();
+    let start = Instant::now();
+
+    let outcome = ssh::ssh_args(vps, inventory).and_then(|args| {
+        let mut batch = ssh::batch_args(ssh::CONNECT_TIMEOUT);
+        batch.extend(args);
+        ShellCommand::from_remote_script(&ssh::destination(vps), &batch, script, sudo)
+            .with_label(&id)
+            .with_timeout(timeout)
+            .run_with_status()
+    });
+
+    let (code, error) = match outcome {
+        Ok((status, captured)) => {
+            if !status.success() && output::is_quiet() {
+                captured.dump(&id);
+            }
+            (status.code(), None)
+        }
+        Err(e) => (None, Some(e.to_string())),
+    };
+
+    HostResult {
+        id,
+        code,
+        duration: start.elapsed(),
+        error,
+    }
+}
+
+#[derive(Tabled)]
+struct HostDisplay {
+    #[tabled(rename = "VPS")]
+    id: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Exit code")]
+    code: String,
+    #[tabled(rename = "Duration")]
+    duration: String,
+    #[tabled(rename = "Details")]
+    error: String,
+}
+
+/// Prints a table of `results` with a count of failed hosts.
+pub fn print_summary(action: &str, results: &[HostResult]) {
+    let rows: Vec<HostDisplay> = results
+        .iter()
+        .map(|r| HostDisplay {
+            id: r.id.clone(),
+            status: if r.success() {
+                "ok".green().to_string()
+            } else {
+                "failed".red().to_string()
+            },
+            code: r.code.map(|c| c.to_string()).unwrap_or_else(|| "-".into()),
+            duration: format!("{:.2}s", r.duration.as_secs_f64()),
+            error: r.error.clone().unwrap_or_else(|| "-".into()),
+        })
+        .collect();
+
+    let mut table = Table::new(rows);
+    table.with(Style::modern_rounded());
+    println!("\n📋 {action} summary:\n{table}");
+
+    let failed = results.iter().filter(|r| !r.success()).count();
+    println!("{} succeeded, {} failed", results.len() - failed, failed);
+}
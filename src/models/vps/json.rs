@@ -3,9 +3,13 @@ use quick_xml::se::to_string as to_xml;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_yaml;
-use std::{fmt, fs, io, path::PathBuf};
+use std::{collections::BTreeMap, fmt, fs, io, path::PathBuf};
 
-use crate::{core::expand_home, log_info, models::vps::generate_id};
+use crate::{
+    core::expand_home,
+    log_info,
+    models::vps::{generate_id, tunnel::TunnelSpec},
+};
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct VpsConfig {
@@ -22,40 +26,43 @@ impl From<super::xml::VpsConfigXml> for VpsConfig {
 
 impl From<VpsConfig> for super::xml::VpsConfigXml {
     fn from(config: VpsConfig) -> Self {
-        super::xml::VpsConfigXml {
-            vps: config
-                .vps
-                .into_iter()
-                .map(|entry| super::xml::VpsEntryXml {
-                    id: entry.id,
-                    name: entry.name,
-                    host: entry.host,
-                    user: entry.user,
-                    port: entry.port.map(String::from), // convert FlexibleValue to String
-                    private_key: entry.private_key,
-                    post_connect_script: entry.post_connect_script,
-                })
-                .collect(),
-        }
+        (&config).into()
     }
 }
 
 impl From<&VpsConfig> for super::xml::VpsConfigXml {
     fn from(config: &VpsConfig) -> Self {
         super::xml::VpsConfigXml {
-            vps: config
-                .vps
+            vps: config.vps.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&VpsEntry> for super::xml::VpsEntryXml {
+    fn from(entry: &VpsEntry) -> Self {
+        super::xml::VpsEntryXml {
+            id: entry.id.clone(),
+            name: entry.name.clone(),
+            host: entry.host.clone(),
+            user: entry.user.clone(),
+            port: entry.port.as_ref().map(String::from),
+            private_key: entry.private_key.clone(),
+            post_connect_script: entry.post_connect_script.clone(),
+            tag: entry.tags.clone(),
+            proxy_jump: entry.proxy_jump.clone(),
+            local_forward: entry.local_forwards.clone(),
+            remote_forward: entry.remote_forwards.clone(),
+            dynamic_forward: entry.dynamic_forward.clone(),
+            forward_agent: entry.forward_agent,
+            ssh_option: entry
+                .ssh_options
                 .iter()
-                .map(|entry| super::xml::VpsEntryXml {
-                    id: entry.id.clone(),
-                    name: entry.name.clone(),
-                    host: entry.host.clone(),
-                    user: entry.user.clone(),
-                    port: entry.port.as_ref().map(String::from),
-                    private_key: entry.private_key.clone(),
-                    post_connect_script: entry.post_connect_script.clone(),
+                .map(|(name, value)| super::xml::SshOptionXml {
+                    name: name.clone(),
+                    value: value.clone(),
                 })
                 .collect(),
+            tunnel: entry.tunnels.clone(),
         }
     }
 }
@@ -75,10 +82,23 @@ impl VpsConfig {
     pub fn validate(&self) -> Result<(), String> {
         for vps in &self.vps {
             vps.validate()?;
+            if let Some(ref jump) = vps.proxy_jump
+                && !self.vps.iter().any(|v| v.id.as_ref() == Some(jump))
+            {
+                return Err(format!(
+                    "VPS `{}` jumps through unknown VPS `{}`",
+                    vps.effective_id(),
+                    jump
+                ));
+            }
         }
         Ok(())
     }
 
+    pub fn find(&self, id: &str) -> Option<&VpsEntry> {
+        self.vps.iter().find(|v| v.id.as_deref() == Some(id))
+    }
+
     pub fn push(&mut self, vps: VpsEntry) {
         self.vps.push(vps);
     }
@@ -150,7 +170,7 @@ impl VpsConfig {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct VpsEntry {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
@@ -165,6 +185,29 @@ pub struct VpsEntry {
     pub private_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub post_connect_script: Option<String>,
+    /// Groups to select the entry by, e.g. `web` or `prod`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Id of the entry to connect through, e.g. a bastion
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy_jump: Option<String>,
+    /// `ssh -L` specs, e.g. `5432:localhost:5432`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub local_forwards: Vec<String>,
+    /// `ssh -R` specs, e.g. `8080:localhost:3000`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remote_forwards: Vec<String>,
+    /// `ssh -D` SOCKS port, optionally with a bind address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dynamic_forward: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forward_agent: Option<bool>,
+    /// Extra `ssh -o` options, e.g. `ServerAliveInterval: "30"`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ssh_options: BTreeMap<String, String>,
+    /// Named forwards started together with `vps tunnel`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tunnels: Vec<TunnelSpec>,
 }
 
 // Conversion from XML to the flexible model
@@ -178,6 +221,18 @@ impl From<super::xml::VpsEntryXml> for VpsEntry {
             port: xml.port.map(FlexibleValue::String),
             private_key: xml.private_key,
             post_connect_script: xml.post_connect_script,
+            tags: xml.tag,
+            proxy_jump: xml.proxy_jump,
+            local_forwards: xml.local_forward,
+            remote_forwards: xml.remote_forward,
+            dynamic_forward: xml.dynamic_forward,
+            forward_agent: xml.forward_agent,
+            ssh_options: xml
+                .ssh_option
+                .into_iter()
+                .map(|opt| (opt.name, opt.value))
+                .collect(),
+            tunnels: xml.tunnel,
         }
     }
 }
@@ -272,7 +327,7 @@ impl VpsEntry {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum FlexibleValue {
     Number(u16),
@@ -1,5 +1,13 @@
+pub mod facts;
+pub mod fleet;
 pub mod json;
+pub mod provision;
 pub mod schema;
+pub mod ssh;
+pub mod ssh_config;
+pub mod status;
+pub mod transfer;
+pub mod tunnel;
 pub mod xml;
 
 pub enum SchemaExample {
@@ -27,6 +35,7 @@ pub fn schema_example(xml: bool) -> SchemaExample {
         port: Some(json::FlexibleValue::from(22)),
         private_key: Some("/home/user/.ssh/id_rsa".into()),
         post_connect_script: Some("uptime && echo $USER".into()),
+        ..json::VpsEntry::default()
     };
 
     let schema = json::VpsConfig { vps: vec![vps] };
@@ -0,0 +1,370 @@
+// Module: Model/VPS/Provision
+// Location: cli/src/model/vps/provision.rs
+use std::{borrow::Cow, io};
+
+use os_info::Type as OSType;
+
+use crate::{
+    core::{
+        shell::{ShellCommand, quote::quote_posix},
+        template::{TemplateContext, TemplateError},
+    },
+    models::{
+        application::{
+            Application, InstallMethod, SelectedInstall, plan_steps, print_plan, step::InstallStep,
+        },
+        category::Category,
+        package_manager::{PackageManager, parse_installed_version, version_matches},
+        repository::refresh_command,
+        system::OsSupport,
+        vps::facts::RemoteFacts,
+    },
+    print_info,
+};
+
+/// The `os_info` type for the distribution in `facts`, matched on the
+/// os-release `ID` first and then on each `ID_LIKE`.
+pub fn remote_os_type(facts: &RemoteFacts) -> Option<OSType> {
+    facts
+        .distro
+        .iter()
+        .chain(&facts.distro_like)
+        .find_map(|id| os_type_for(id))
+}
+
+fn os_type_for(id: &str) -> Option<OSType> {
+    Some(match id.to_lowercase().as_str() {
+        "ubuntu" => OSType::Ubuntu,
+        "debian" => OSType::Debian,
+        "raspbian" => OSType::Raspbian,
+        "linuxmint" => OSType::Mint,
+        "pop" => OSType::Pop,
+        "elementary" => OSType::Elementary,
+        "zorin" => OSType::Zorin,
+        "kali" => OSType::Kali,
+        "fedora" => OSType::Fedora,
+        "rhel" => OSType::RedHatEnterprise,
+        "centos" => OSType::CentOS,
+        "rocky" => OSType::RockyLinux,
+        "almalinux" => OSType::AlmaLinux,
+        "amzn" => OSType::Amazon,
+        "ol" => OSType::OracleLinux,
+        "sles" | "suse" => OSType::SUSE,
+        id if id.starts_with("opensuse") => OSType::openSUSE,
+        "arch" => OSType::Arch,
+        "manjaro" => OSType::Manjaro,
+        "endeavouros" => OSType::EndeavourOS,
+        "alpine" => OSType::Alpine,
+        "void" => OSType::Void,
+        "gentoo" => OSType::Gentoo,
+        "nixos" => OSType::NixOS,
+        _ => return None,
+    })
+}
+
+/// Server compatible Linux apps matching any of `names` (id or name) and
+/// any of `categories`. Empty filters match everything.
+pub fn server_apps<'a>(
+    apps: &'a [Application],
+    names: &[String],
+    categories: &[Category],
+) -> Vec<&'a Application> {
+    apps.iter()
+        .filter(|app| app.server_compatible)
+        .filter(|app| {
+            app.supported_systems
+                .iter()
+                .any(|s| s.flags().contains(OsSupport::LINUX))
+        })
+        .filter(|app| {
+            names.is_empty()
+                || names.iter().any(|n| {
+                    app.id.as_ref().is_some_and(|id| id.eq_ignore_ascii_case(n))
+                        || app.name.eq_ignore_ascii_case(n)
+                })
+        })
+        .filter(|app| {
+            categories.is_empty() || app.categories.iter().any(|c| categories.contains(c))
+        })
+        .collect()
+}
+
+/// Whether `method` can run on the host described by `facts`.
+///
+/// Vendor repositories are written locally and AUR helpers build
+/// interactively, so methods using either are left to `app install`.
+pub fn usable_remotely(method: &InstallMethod, facts: &RemoteFacts) -> bool {
+    method.repository.is_none() && runs_remotely(method, facts)
+}
+
+/// Whether `method` could run on the host, its vendor repository aside.
+fn runs_remotely(method: &InstallMethod, facts: &RemoteFacts) -> bool {
+    match method.package_manager {
+        Some(PackageManager::Aur | PackageManager::Yay) => false,
+        Some(pm) if method.steps.is_none() => facts.available_package_managers.contains(&pm),
+        _ => true,
+    }
+}
+
+/// Picks the install method for `app` on the remote host, preferring
+/// methods for its distribution over ones for Linux in general.
+pub fn select_remote<'a>(
+    app: &'a Application,
+    facts: &RemoteFacts,
+    os: OSType,
+) -> Option<SelectedInstall<'a>> {
+    [os, OSType::Linux].into_iter().find_map(|os| {
+        app.select_for(
+            os,
+            |method| usable_remotely(method, facts),
+            |version| TemplateContext::for_remote(facts, os, version),
+        )
+    })
+}
+
+/// Why [`select_remote`] found no method for `app`: a method needing a
+/// vendor repository is named, since `app install` could still use it.
+pub fn no_method_reason(app: &Application, facts: &RemoteFacts, os: OSType) -> String {
+    let needs_repository = [os, OSType::Linux].into_iter().any(|os| {
+        app.select_for(
+            os,
+            |method| method.repository.is_some() && runs_remotely(method, facts),
+            |version| TemplateContext::for_remote(facts, os, version),
+        )
+        .is_some()
+    });
+    if needs_repository {
+        "vendor repository not supported remotely".into()
+    } else {
+        format!("no install method for {os}")
+    }
+}
+
+/// How to tell whether an app is already installed on the remote host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresenceCheck {
+    /// Exits 0 when the check binary is on the PATH and reports the
+    /// expected version, printing the pinned package's version if any
+    pub script: String,
+    pin: Option<(PackageManager, String, String)>,
+}
+
+impl PresenceCheck {
+    /// Whether `stdout` of the script shows the pinned version, compared
+    /// as `app install` does locally.
+    pub fn matches_pin(&self, stdout: &str) -> bool {
+        match &self.pin {
+            Some((pm, package, wanted)) => parse_installed_version(*pm, stdout, package)
+                .is_none_or(|have| version_matches(&have, wanted)),
+            None => true,
+        }
+    }
+}
+
+/// The same checks `app install` makes locally: the check binary and its
+/// expected version, then the installed version of a pinned package.
+pub fn presence_script(selection: &SelectedInstall) -> Option<PresenceCheck> {
+    let mut tests = Vec::new();
+    if let Some(check) = selection.version.check_command.as_deref() {
+        tests.push(binary_test(check));
+        if let Some(expected) = selection.version.expected_version.as_deref() {
+            tests.push(version_test(check, expected));
+        }
+    }
+
+    // Brew encodes the pin in the package name
+    let pin = selection
+        .method
+        .package_version
+        .as_deref()
+        .zip(selection.package())
+        .filter(|(_, (pm, _))| *pm != PackageManager::Brew)
+        .and_then(|(wanted, (pm, name))| {
+            let query = pm.installed_version_query(&name)?;
+            tests.push(command_line(&query, true));
+            Some((pm, name, wanted.to_string()))
+        });
+
+    (!tests.is_empty()).then(|| PresenceCheck {
+        script: tests.join(" && "),
+        pin,
+    })
+}
+
+/// The script that installs one app on the remote host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstallScript {
+    pub script: String,
+    /// Some line escalates with `sudo`, which may prompt for a password
+    pub uses_sudo: bool,
+}
+
+/// Builds the POSIX script that installs `selection` on the remote host.
+///
+/// Lines come from the same commands `app install` runs locally, rendered
+/// for the remote shell. Commands needing privileges escalate with `sudo`
+/// unless the remote user is `root`. The script ends by verifying the
+/// check command.
+pub fn install_script(selection: &SelectedInstall, root: bool) -> io::Result<InstallScript> {
+    let ctx = &selection.context;
+    let method = selection.method;
+    let mut lines = vec!["set -e".to_string()];
+    let mut uses_sudo = false;
+    let mut line = |cmd: &ShellCommand| {
+        uses_sudo |= cmd.requires_sudo && !root;
+        command_line(cmd, root)
+    };
+
+    if let Some(steps) = &method.steps {
+        for (_, phase) in steps.install_phases() {
+            for step in phase {
+                let command = line(&step.to_command(ctx).map_err(invalid_template)?);
+                let guards = guard_tests(step, ctx, root).map_err(invalid_template)?;
+                lines.push(if guards.is_empty() {
+                    command
+                } else {
+                    format!("if {}; then {command}; fi", guards.join(" && "))
+                });
+            }
+        }
+    } else if let (Some(pm), Some(pkg)) = (method.package_manager, method.package_name.as_deref()) {
+        let pkg = ctx.render(pkg).map_err(invalid_template)?;
+        let request = method.package_request(&pkg);
+        // A fresh host may never have fetched the index
+        if let Ok(refresh) = refresh_command(pm) {
+            lines.push(line(&refresh));
+        }
+        for cmd in pm.prepare_commands(&request) {
+            lines.push(line(&cmd));
+        }
+        lines.push(line(&pm.install_command(&request)?));
+        // As locally, a hold that can't be set only warns
+        if let Some(hold) = pm.hold_command(&request) {
+            lines.push(format!(
+                "{} || echo {} >&2",
+                line(&hold),
+                quote_posix("warning: failed to hold the package version")
+            ));
+        }
+    } else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no install steps or package manager",
+        ));
+    }
+
+    if let Some(check) = selection.version.check_command.as_deref() {
+        let binary = check.split_whitespace().next().unwrap_or(check);
+        lines.push(format!(
+            "{} || {{ echo {} >&2; exit 1; }}",
+            binary_test(check),
+            quote_posix(&format!("`{binary}` was not found on the PATH"))
+        ));
+        if let Some(expected) = selection.version.expected_version.as_deref() {
+            lines.push(format!(
+                "{} || {{ echo {} >&2; exit 1; }}",
+                version_test(check, expected),
+                quote_posix(&format!("`{check}` did not report version {expected}"))
+            ));
+        }
+    }
+
+    Ok(InstallScript {
+        script: lines.join("\n"),
+        uses_sudo,
+    })
+}
+
+/// Prints what installing `selection` on the remote host would do: the
+/// step plan for steps, else the script lines.
+pub fn print_install_plan(selection: &SelectedInstall, root: bool) -> io::Result<()> {
+    match &selection.method.steps {
+        Some(steps) => {
+            print_info!("💡 [Dry Run] Step plan:");
+            print_plan(&plan_steps(&selection.context, &steps.install_phases()));
+        }
+        None => {
+            for line in install_script(selection, root)?.script.lines().skip(1) {
+                print_info!("(dry run) Would run: {}", line);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Succeeds when the first word of `check` resolves on the PATH.
+fn binary_test(check: &str) -> String {
+    let binary = check.split_whitespace().next().unwrap_or(check);
+    format!("command -v {} >/dev/null 2>&1", quote_posix(binary))
+}
+
+/// Succeeds when the output of `check` contains `expected`.
+fn version_test(check: &str, expected: &str) -> String {
+    format!("{check} 2>&1 | grep -qF -- {}", quote_posix(expected))
+}
+
+/// A command as one remote shell line, with its env, timeout and working
+/// directory applied by the remote shell.
+fn command_line(cmd: &ShellCommand, root: bool) -> String {
+    let mut line = String::new();
+    if cmd.requires_sudo && !root {
+        line.push_str("sudo ");
+    }
+    if !cmd.env.is_empty() {
+        line.push_str("env ");
+        for (k, v) in &cmd.env {
+            line.push_str(&format!("{k}={} ", quote_path(v)));
+        }
+    }
+    if let Some(timeout) = cmd.timeout {
+        line.push_str(&format!("timeout {} ", timeout.as_secs()));
+    }
+    line.push_str(&quote_posix(&cmd.command));
+    for arg in &cmd.args {
+        line.push(' ');
+        line.push_str(&quote_posix(arg));
+    }
+
+    match &cmd.cwd {
+        Some(dir) => format!("( cd {} && {line} )", quote_path(&dir.to_string_lossy())),
+        None => line,
+    }
+}
+
+/// The step's guards as shell tests that must all pass for it to run.
+fn guard_tests(
+    step: &InstallStep,
+    ctx: &TemplateContext,
+    root: bool,
+) -> Result<Vec<String>, TemplateError> {
+    let guards = step.guards(ctx)?;
+    let mut tests = Vec::new();
+    if let Some(path) = guards.creates {
+        tests.push(format!("[ ! -e {} ]", quote_path(&path)));
+    }
+    if let Some(cmd) = guards.only_if {
+        tests.push(command_line(&cmd, root));
+    }
+    if let Some(cmd) = guards.unless {
+        tests.push(format!("! {}", command_line(&cmd, root)));
+    }
+    Ok(tests)
+}
+
+/// Quotes `path`, leaving a leading `~` or `$HOME` for the remote shell to
+/// expand.
+fn quote_path(path: &str) -> Cow<'_, str> {
+    let rest = path.strip_prefix("$HOME").or_else(|| {
+        path.strip_prefix('~')
+            .filter(|r| r.is_empty() || r.starts_with('/'))
+    });
+    match rest {
+        Some("") => Cow::Borrowed("\"$HOME\""),
+        Some(rest) => Cow::Owned(format!("\"$HOME\"{}", quote_posix(rest))),
+        None => quote_posix(path),
+    }
+}
+
+fn invalid_template(e: TemplateError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, e)
+}
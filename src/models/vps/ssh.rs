@@ -0,0 +1,155 @@
+// Module: Model/VPS/Ssh
+// Location: cli/src/model/vps/ssh.rs
+use std::{io, time::Duration};
+
+use crate::{core::shell::quote, models::vps::json::VpsEntry};
+
+/// How long unattended runs wait for ssh to connect.
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Options that make ssh fail instead of prompting, for runs nobody is
+/// watching.
+pub fn batch_args(connect_timeout: Duration) -> Vec<String> {
+    vec![
+        "-o".into(),
+        "BatchMode=yes".into(),
+        "-o".into(),
+        format!("ConnectTimeout={}", connect_timeout.as_secs().max(1)),
+    ]
+}
+
+/// `user@host` as ssh expects it.
+pub fn destination(vps: &VpsEntry) -> String {
+    format!("{}@{}", vps.effective_user(), vps.host)
+}
+
+/// Options deciding how ssh reaches `vps`, except the port: identity, jump
+/// host, agent forwarding and `ssh_options`.
+///
+/// Left out so callers can pass it as `-p` (ssh, rsync) or `-P` / a URI (scp).
+pub fn connection_args(vps: &VpsEntry, inventory: &[VpsEntry]) -> io::Result<Vec<String>> {
+    connection_args_via(vps, inventory, &mut Vec::new())
+}
+
+/// [`connection_args`] plus `-p` when the port isn't 22.
+pub fn ssh_args(vps: &VpsEntry, inventory: &[VpsEntry]) -> io::Result<Vec<String>> {
+    ssh_args_via(vps, inventory, &mut Vec::new())
+}
+
+fn ssh_args_via(
+    vps: &VpsEntry,
+    inventory: &[VpsEntry],
+    seen: &mut Vec<String>,
+) -> io::Result<Vec<String>> {
+    let mut args = connection_args_via(vps, inventory, seen)?;
+    if vps.effective_port() != 22 {
+        args.extend(["-p".into(), vps.effective_port().to_string()]);
+    }
+    Ok(args)
+}
+
+fn connection_args_via(
+    vps: &VpsEntry,
+    inventory: &[VpsEntry],
+    seen: &mut Vec<String>,
+) -> io::Result<Vec<String>> {
+    let mut args = Vec::new();
+    if let Some(ref key) = vps.private_key {
+        args.extend(["-i".into(), key.clone()]);
+    }
+    if let Some(ref jump) = vps.proxy_jump {
+        seen.push(vps.effective_id().to_string());
+        args.extend([
+            "-o".into(),
+            format!("ProxyCommand={}", proxy_command(jump, inventory, seen)?),
+        ]);
+    }
+    if let Some(agent) = vps.forward_agent {
+        args.push(if agent { "-A" } else { "-a" }.into());
+    }
+    for (name, value) in &vps.ssh_options {
+        args.extend(["-o".into(), format!("{name}={value}")]);
+    }
+    Ok(args)
+}
+
+/// The `ProxyCommand` tunnelling through the entry `jump_id`.
+///
+/// Unlike `-J`, this lets each jump host use its own key and port, and
+/// follows the jump host's own `proxy_jump`. ssh expands `%` tokens in the
+/// whole command, so a nested jump host's command has them doubled.
+fn proxy_command(
+    jump_id: &str,
+    inventory: &[VpsEntry],
+    seen: &mut Vec<String>,
+) -> io::Result<String> {
+    if seen.iter().any(|id| id == jump_id) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("proxy_jump loop: {} -> {}", seen.join(" -> "), jump_id),
+        ));
+    }
+    let jump = inventory
+        .iter()
+        .find(|v| v.id.as_deref() == Some(jump_id))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("proxy_jump refers to unknown VPS '{jump_id}'"),
+            )
+        })?;
+
+    let mut args = vec!["ssh".to_string()];
+    args.extend(ssh_args_via(jump, inventory, seen)?);
+    args.push(destination(jump));
+    let escaped = quote::join_posix(args).replace('%', "%%");
+    Ok(format!("{escaped} -W %h:%p"))
+}
+
+/// `-L`, `-R` and `-D` options for a set of forwards. Only interactive
+/// sessions and tunnels use these; scripts and copies would fight over the
+/// same local ports.
+pub fn forward_args(local: &[String], remote: &[String], dynamic: Option<&str>) -> Vec<String> {
+    let mut args = Vec::new();
+    for spec in local {
+        args.extend(["-L".into(), spec.clone()]);
+    }
+    for spec in remote {
+        args.extend(["-R".into(), spec.clone()]);
+    }
+    if let Some(spec) = dynamic {
+        args.extend(["-D".into(), spec.to_string()]);
+    }
+    args
+}
+
+/// Converts an `ssh -L`/`-R` spec to its ssh_config form, which separates
+/// the listening side from the target with a space:
+/// `127.0.0.1:5432:db:5432` becomes `127.0.0.1:5432 db:5432`.
+pub fn forward_directive(spec: &str) -> String {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in spec.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ':' if depth == 0 => {
+                parts.push(&spec[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&spec[start..]);
+
+    if parts.len() < 3 {
+        return spec.to_string();
+    }
+    let target = parts.len() - 2;
+    format!(
+        "{} {}",
+        parts[..target].join(":"),
+        parts[target..].join(":")
+    )
+}
@@ -0,0 +1,210 @@
+// Module: Model/VPS/SshConfig
+// Location: cli/src/model/vps/ssh_config.rs
+use crate::models::vps::{
+    generate_id,
+    json::{FlexibleValue, VpsEntry},
+    ssh::forward_directive,
+};
+
+pub const BLOCK_BEGIN: &str = "# BEGIN tranquility managed hosts";
+pub const BLOCK_END: &str = "# END tranquility managed hosts";
+
+/// A `Host` entry read from an ssh config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SshHost {
+    pub alias: String,
+    pub host_name: Option<String>,
+    pub user: Option<String>,
+    pub port: Option<u16>,
+    pub identity_file: Option<String>,
+}
+
+impl SshHost {
+    /// Converts the entry to a VPS, using the alias as its name. The alias
+    /// is kept as the id unless one of `existing` already uses it.
+    pub fn into_vps(self, existing: &[VpsEntry]) -> VpsEntry {
+        let host = self.host_name.unwrap_or_else(|| self.alias.clone());
+        let taken = existing
+            .iter()
+            .any(|v| v.id.as_deref() == Some(self.alias.as_str()));
+        let id = if taken {
+            generate_id(Some(&self.alias), &host, self.user.as_deref())
+        } else {
+            self.alias.clone()
+        };
+        VpsEntry {
+            id: Some(id),
+            name: Some(self.alias),
+            host,
+            user: self.user,
+            port: self.port.map(FlexibleValue::from),
+            private_key: self.identity_file,
+            ..VpsEntry::default()
+        }
+    }
+}
+
+/// Renders the managed block of `Host` stanzas, one per entry.
+pub fn render_block(entries: &[VpsEntry]) -> String {
+    let mut block = format!("{BLOCK_BEGIN}\n");
+    for vps in entries {
+        block.push_str(&format!("Host {}\n", vps.effective_id()));
+        block.push_str(&format!("    HostName {}\n", vps.host));
+        block.push_str(&format!("    User {}\n", vps.effective_user()));
+        if vps.effective_port() != 22 {
+            block.push_str(&format!("    Port {}\n", vps.effective_port()));
+        }
+        if let Some(ref key) = vps.private_key {
+            block.push_str(&format!("    IdentityFile {}\n", quote_value(key)));
+        }
+        // Jump hosts are exported too, so their id works as an alias here
+        if let Some(ref jump) = vps.proxy_jump {
+            block.push_str(&format!("    ProxyJump {jump}\n"));
+        }
+        for spec in &vps.local_forwards {
+            block.push_str(&format!("    LocalForward {}\n", forward_directive(spec)));
+        }
+        for spec in &vps.remote_forwards {
+            block.push_str(&format!("    RemoteForward {}\n", forward_directive(spec)));
+        }
+        if let Some(ref spec) = vps.dynamic_forward {
+            block.push_str(&format!("    DynamicForward {spec}\n"));
+        }
+        if let Some(agent) = vps.forward_agent {
+            let value = if agent { "yes" } else { "no" };
+            block.push_str(&format!("    ForwardAgent {value}\n"));
+        }
+        for (name, value) in &vps.ssh_options {
+            block.push_str(&format!("    {} {}\n", name, quote_value(value)));
+        }
+        block.push('\n');
+    }
+    block.push_str(BLOCK_END);
+    block.push('\n');
+    block
+}
+
+fn quote_value(value: &str) -> String {
+    if value.contains(char::is_whitespace) {
+        format!("\"{value}\"")
+    } else {
+        value.to_string()
+    }
+}
+
+/// Replaces the managed block in `existing`, leaving every other line as
+/// it was.
+///
+/// Without a block yet, it goes before the first `Host` or `Match` line:
+/// options above that line are global and must stay outside any stanza.
+/// A block missing its end marker runs to the end of the file.
+pub fn merge_block(existing: &str, block: &str) -> String {
+    let lines: Vec<&str> = existing.lines().collect();
+    let begin = lines.iter().position(|l| l.trim() == BLOCK_BEGIN);
+
+    let (before, after) = match begin {
+        Some(b) => {
+            let end = lines[b..]
+                .iter()
+                .position(|l| l.trim() == BLOCK_END)
+                .map_or(lines.len(), |e| b + e + 1);
+            (&lines[..b], &lines[end..])
+        }
+        None => {
+            let at = lines
+                .iter()
+                .position(|l| {
+                    let keyword = split_line(l).map(|(k, _)| k.to_ascii_lowercase());
+                    matches!(keyword.as_deref(), Some("host" | "match"))
+                })
+                .unwrap_or(lines.len());
+            (&lines[..at], &lines[at..])
+        }
+    };
+
+    let mut merged = String::new();
+    for line in before {
+        merged.push_str(line);
+        merged.push('\n');
+    }
+    if !merged.is_empty() && !merged.ends_with("\n\n") {
+        merged.push('\n');
+    }
+    merged.push_str(block);
+    if !after.is_empty() {
+        if !after[0].trim().is_empty() {
+            merged.push('\n');
+        }
+        for line in after {
+            merged.push_str(line);
+            merged.push('\n');
+        }
+    }
+    merged
+}
+
+/// Splits a config line into keyword and value, accepting both
+/// `Keyword value` and `Keyword=value`. Blank lines and comments yield `None`.
+fn split_line(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let split = line
+        .find(|c: char| c.is_whitespace() || c == '=')
+        .unwrap_or(line.len());
+    let (keyword, rest) = line.split_at(split);
+    let value = rest.trim_start().trim_start_matches('=').trim();
+    Some((keyword, value.trim_matches('"')))
+}
+
+fn is_pattern(alias: &str) -> bool {
+    alias.contains(['*', '?', '!'])
+}
+
+/// Parses the `Host` entries of an ssh config. Wildcard patterns and
+/// `Match` blocks are skipped; a `Host` line naming several aliases
+/// yields one entry per alias.
+pub fn parse_hosts(content: &str) -> Vec<SshHost> {
+    let mut hosts: Vec<SshHost> = Vec::new();
+    // Indexes into `hosts` the current stanza applies to
+    let mut current: Vec<usize> = Vec::new();
+
+    for line in content.lines() {
+        let Some((keyword, value)) = split_line(line) else {
+            continue;
+        };
+        match keyword.to_ascii_lowercase().as_str() {
+            "host" => {
+                current.clear();
+                for alias in value.split_whitespace().filter(|a| !is_pattern(a)) {
+                    current.push(hosts.len());
+                    hosts.push(SshHost {
+                        alias: alias.to_string(),
+                        ..SshHost::default()
+                    });
+                }
+            }
+            "match" => current.clear(),
+            key => {
+                for &i in &current {
+                    let host = &mut hosts[i];
+                    // ssh uses the first value it sees for each option
+                    match key {
+                        "hostname" if host.host_name.is_none() => {
+                            host.host_name = Some(value.to_string())
+                        }
+                        "user" if host.user.is_none() => host.user = Some(value.to_string()),
+                        "port" if host.port.is_none() => host.port = value.parse().ok(),
+                        "identityfile" if host.identity_file.is_none() => {
+                            host.identity_file = Some(value.to_string())
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    hosts
+}
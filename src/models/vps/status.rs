@@ -0,0 +1,138 @@
+// Module: Model/VPS/Status
+// Location: cli/src/model/vps/status.rs
+use std::{
+    io::{self, Read},
+    net::{TcpStream, ToSocketAddrs},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{
+    core::shell::ShellCommand,
+    models::vps::{json::VpsEntry, ssh},
+};
+
+/// What `vps status` found out about one entry.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VpsStatus {
+    pub id: String,
+    pub host: String,
+    pub port: u16,
+    /// `None` when the entry sits behind a jump host and can't be probed
+    /// directly
+    pub reachable: Option<bool>,
+    pub latency_ms: Option<f64>,
+    /// The server's identification line, e.g. `SSH-2.0-OpenSSH_9.6`
+    pub banner: Option<String>,
+    /// Whether key auth works, when it was checked
+    pub auth: Option<bool>,
+    pub error: Option<String>,
+}
+
+impl VpsStatus {
+    /// Nothing checked failed.
+    pub fn healthy(&self) -> bool {
+        self.reachable != Some(false) && self.auth != Some(false)
+    }
+}
+
+/// Connects to `host:port`, trying each resolved address in turn, and
+/// reads the server's first line. Returns the connect latency and the
+/// banner, if one arrived within `timeout`.
+pub fn probe_tcp(
+    host: &str,
+    port: u16,
+    timeout: Duration,
+) -> io::Result<(Duration, Option<String>)> {
+    let mut last_error = io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{host} did not resolve to any address"),
+    );
+
+    for addr in (host, port).to_socket_addrs()? {
+        let start = Instant::now();
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(stream) => {
+                let latency = start.elapsed();
+                return Ok((latency, read_banner(stream, timeout)));
+            }
+            Err(e) => last_error = e,
+        }
+    }
+    Err(last_error)
+}
+
+/// SSH servers send their identification first; anything else, or
+/// silence, yields `None`.
+fn read_banner(mut stream: TcpStream, timeout: Duration) -> Option<String> {
+    stream.set_read_timeout(Some(timeout)).ok()?;
+    let mut buf = Vec::new();
+    let mut byte = [0u8; 1];
+    // The identification line is at most 255 bytes (RFC 4253)
+    while buf.len() < 255 {
+        match stream.read(&mut byte) {
+            Ok(1) if byte[0] == b'\n' => break,
+            Ok(1) => buf.push(byte[0]),
+            _ => break,
+        }
+    }
+
+    let line = String::from_utf8_lossy(&buf).trim_end().to_string();
+    (!line.is_empty()).then_some(line)
+}
+
+/// Whether `ssh ... true` succeeds without prompting, i.e. key auth works.
+pub fn check_auth(vps: &VpsEntry, inventory: &[VpsEntry], timeout: Duration) -> io::Result<bool> {
+    let mut args = ssh::batch_args(timeout);
+    args.extend(ssh::ssh_args(vps, inventory)?);
+    args.push(ssh::destination(vps));
+    args.push("true".into());
+
+    // Jump hosts add their own connect time on top
+    let hops = 1 + u32::from(vps.proxy_jump.is_some());
+    Ok(ShellCommand::new("ssh")
+        .with_args(args)
+        .with_timeout(Some(timeout * hops + Duration::from_secs(5)))
+        .succeeds())
+}
+
+/// Probes `vps` over TCP and, with `auth`, checks that key auth works.
+///
+/// Entries behind a jump host usually aren't reachable directly, so only
+/// the auth check applies to them.
+pub fn probe(vps: &VpsEntry, inventory: &[VpsEntry], timeout: Duration, auth: bool) -> VpsStatus {
+    let mut status = VpsStatus {
+        id: vps.effective_id().to_string(),
+        host: vps.host.clone(),
+        port: vps.effective_port(),
+        reachable: None,
+        latency_ms: None,
+        banner: None,
+        auth: None,
+        error: None,
+    };
+
+    if vps.proxy_jump.is_none() {
+        match probe_tcp(&vps.host, status.port, timeout) {
+            Ok((latency, banner)) => {
+                status.reachable = Some(true);
+                status.latency_ms = Some(latency.as_secs_f64() * 1000.0);
+                status.banner = banner;
+            }
+            Err(e) => {
+                status.reachable = Some(false);
+                status.error = Some(e.to_string());
+                return status;
+            }
+        }
+    }
+
+    if auth {
+        match check_auth(vps, inventory, timeout) {
+            Ok(ok) => status.auth = Some(ok),
+            Err(e) => status.error = Some(e.to_string()),
+        }
+    }
+    status
+}
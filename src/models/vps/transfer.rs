@@ -0,0 +1,146 @@
+// Module: Model/VPS/Transfer
+// Location: cli/src/model/vps/transfer.rs
+use std::io;
+
+use strum::Display;
+
+use crate::{
+    core::shell::{ShellCommand, quote},
+    models::vps::{json::VpsEntry, ssh},
+};
+
+/// Program used to move files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum TransferTool {
+    Rsync,
+    Scp,
+}
+
+/// One side of a copy.
+#[derive(Debug, Clone, Copy)]
+pub enum Endpoint<'a> {
+    Local(&'a str),
+    Remote { vps: &'a VpsEntry, path: &'a str },
+}
+
+impl Endpoint<'_> {
+    fn vps(&self) -> Option<&VpsEntry> {
+        match self {
+            Endpoint::Local(_) => None,
+            Endpoint::Remote { vps, .. } => Some(vps),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TransferOptions {
+    pub recursive: bool,
+    pub progress: bool,
+}
+
+/// `user@host`, bracketing IPv6 addresses the way rsync and scp expect.
+fn user_host(vps: &VpsEntry) -> String {
+    if vps.host.contains(':') {
+        format!("{}@[{}]", vps.effective_user(), vps.host)
+    } else {
+        format!("{}@{}", vps.effective_user(), vps.host)
+    }
+}
+
+/// ssh invocation rsync uses to reach `vps`, honouring its key, port, jump
+/// host and options.
+pub fn rsync_shell(vps: &VpsEntry, inventory: &[VpsEntry]) -> io::Result<String> {
+    let mut args = vec!["ssh".to_string()];
+    args.extend(ssh::ssh_args(vps, inventory)?);
+    Ok(quote::join_posix(args))
+}
+
+/// `scp://` URI for a path on `vps`. A path relative to the home directory
+/// stays relative; an absolute one keeps its leading slash.
+pub fn scp_uri(vps: &VpsEntry, path: &str) -> String {
+    format!("scp://{}:{}/{}", user_host(vps), vps.effective_port(), path)
+}
+
+/// Builds the command copying `from` to `to`. `inventory` resolves jump
+/// hosts.
+///
+/// rsync reaches a single remote through `-e ssh ...`. scp addresses every
+/// remote by URI so two hosts can use different ports, and routes a copy
+/// between two VPS entries through this machine with `-3`. Its other ssh
+/// options apply to both hosts, so those must agree.
+pub fn transfer_command(
+    tool: TransferTool,
+    from: &Endpoint,
+    to: &Endpoint,
+    options: TransferOptions,
+    inventory: &[VpsEntry],
+) -> io::Result<ShellCommand> {
+    let remotes: Vec<&VpsEntry> = [from.vps(), to.vps()].into_iter().flatten().collect();
+    if remotes.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "at least one side of a copy must be a VPS",
+        ));
+    }
+
+    match tool {
+        TransferTool::Rsync => {
+            let [vps] = remotes[..] else {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "rsync cannot copy between two remote hosts; use scp",
+                ));
+            };
+            let spec = |endpoint: &Endpoint| match endpoint {
+                Endpoint::Local(path) => path.to_string(),
+                Endpoint::Remote { vps, path } => format!("{}:{}", user_host(vps), path),
+            };
+
+            let mut args = vec![if options.recursive { "-az" } else { "-ltz" }.to_string()];
+            if options.progress {
+                args.push("--progress".into());
+            }
+            args.extend([
+                "-e".into(),
+                rsync_shell(vps, inventory)?,
+                spec(from),
+                spec(to),
+            ]);
+            Ok(ShellCommand::new("rsync").with_args(args))
+        }
+        TransferTool::Scp => {
+            let connection = remotes
+                .iter()
+                .map(|vps| ssh::connection_args(vps, inventory))
+                .collect::<io::Result<Vec<_>>>()?;
+            let mut args = Vec::new();
+            if remotes.len() == 2 {
+                if connection[0] != connection[1] {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        format!(
+                            "{} and {} use different keys or ssh options; copy through this machine in two steps",
+                            remotes[0].effective_id(),
+                            remotes[1].effective_id()
+                        ),
+                    ));
+                }
+                args.push("-3".to_string());
+            }
+            if options.recursive {
+                args.push("-r".into());
+            }
+            if !options.progress {
+                args.push("-q".into());
+            }
+            args.extend(connection.into_iter().next().unwrap_or_default());
+            let spec = |endpoint: &Endpoint| match endpoint {
+                Endpoint::Local(path) => path.to_string(),
+                Endpoint::Remote { vps, path } => scp_uri(vps, path),
+            };
+            args.extend([spec(from), spec(to)]);
+            Ok(ShellCommand::new("scp").with_args(args))
+        }
+    }
+}
@@ -0,0 +1,193 @@
+// Module: Model/VPS/Tunnel
+// Location: cli/src/model/vps/tunnel.rs
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::shell::ShellCommand,
+    models::vps::{json::VpsEntry, ssh},
+};
+
+/// A named set of forwards on a VPS entry, started with `vps tunnel`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TunnelSpec {
+    pub name: String,
+    /// `ssh -L` specs, e.g. `5433:localhost:5432`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub local_forwards: Vec<String>,
+    /// `ssh -R` specs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remote_forwards: Vec<String>,
+    /// `ssh -D` SOCKS port, optionally with a bind address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dynamic_forward: Option<String>,
+}
+
+impl TunnelSpec {
+    /// The forwards set directly on the entry, as a tunnel named `default`.
+    pub fn from_entry(vps: &VpsEntry) -> Self {
+        Self {
+            name: "default".into(),
+            local_forwards: vps.local_forwards.clone(),
+            remote_forwards: vps.remote_forwards.clone(),
+            dynamic_forward: vps.dynamic_forward.clone(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.local_forwards.is_empty()
+            && self.remote_forwards.is_empty()
+            && self.dynamic_forward.is_none()
+    }
+
+    pub fn forward_args(&self) -> Vec<String> {
+        ssh::forward_args(
+            &self.local_forwards,
+            &self.remote_forwards,
+            self.dynamic_forward.as_deref(),
+        )
+    }
+}
+
+/// The ssh command holding `spec`'s forwards open without a shell.
+///
+/// `ExitOnForwardFailure` makes a taken port fail the start instead of
+/// leaving a useless tunnel, and `BatchMode` fails instead of prompting,
+/// since nothing can answer a prompt in the background.
+pub fn tunnel_command(
+    vps: &VpsEntry,
+    inventory: &[VpsEntry],
+    spec: &TunnelSpec,
+) -> io::Result<ShellCommand> {
+    let mut args: Vec<String> = [
+        "-N",
+        "-o",
+        "ExitOnForwardFailure=yes",
+        "-o",
+        "BatchMode=yes",
+    ]
+    .map(String::from)
+    .to_vec();
+    args.extend(ssh::ssh_args(vps, inventory)?);
+    args.extend(spec.forward_args());
+    args.push(ssh::destination(vps));
+    Ok(ShellCommand::new("ssh").with_args(args))
+}
+
+/// A started tunnel, stored as JSON under the tunnels directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TunnelRecord {
+    pub vps_id: String,
+    pub name: String,
+    pub pid: u32,
+    /// The forward options the tunnel was started with
+    pub forwards: Vec<String>,
+    pub started_at: String,
+}
+
+impl TunnelRecord {
+    /// File stem shared by the record and the tunnel's log.
+    pub fn key(vps_id: &str, name: &str) -> String {
+        format!("{vps_id}--{name}").replace(|c: char| !c.is_alphanumeric() && c != '-', "_")
+    }
+
+    pub fn path(dir: &Path, vps_id: &str, name: &str) -> PathBuf {
+        dir.join(format!("{}.json", Self::key(vps_id, name)))
+    }
+
+    pub fn log_path(dir: &Path, vps_id: &str, name: &str) -> PathBuf {
+        dir.join(format!("{}.log", Self::key(vps_id, name)))
+    }
+
+    pub fn save(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(Self::path(dir, &self.vps_id, &self.name), json)
+    }
+
+    pub fn remove(&self, dir: &Path) -> io::Result<()> {
+        let _ = fs::remove_file(Self::log_path(dir, &self.vps_id, &self.name));
+        match fs::remove_file(Self::path(dir, &self.vps_id, &self.name)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn load(dir: &Path, vps_id: &str, name: &str) -> Option<Self> {
+        let content = fs::read_to_string(Self::path(dir, vps_id, name)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Every record in `dir`, sorted by VPS and name. Unreadable files are
+    /// skipped.
+    pub fn load_all(dir: &Path) -> io::Result<Vec<Self>> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+
+        let mut records: Vec<Self> = entries
+            .filter_map(Result::ok)
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|e| fs::read_to_string(e.path()).ok())
+            .filter_map(|content| serde_json::from_str(&content).ok())
+            .collect();
+        records.sort_by(|a, b| (&a.vps_id, &a.name).cmp(&(&b.vps_id, &b.name)));
+        Ok(records)
+    }
+
+    /// Whether the tunnel's ssh process is still running.
+    pub fn is_alive(&self) -> bool {
+        process_running(self.pid, "ssh")
+    }
+}
+
+/// Whether `pid` is running and its program name contains `name`. Checking
+/// the name keeps a reused PID from passing for a live tunnel.
+pub fn process_running(pid: u32, name: &str) -> bool {
+    let output = if cfg!(windows) {
+        Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {pid}"), "/NH"])
+            .stderr(Stdio::null())
+            .output()
+    } else {
+        Command::new("ps")
+            .args(["-p", &pid.to_string(), "-o", "comm="])
+            .stderr(Stdio::null())
+            .output()
+    };
+
+    output
+        .map(|out| {
+            String::from_utf8_lossy(&out.stdout)
+                .to_lowercase()
+                .contains(name)
+        })
+        .unwrap_or(false)
+}
+
+/// Stops the process `pid`.
+pub fn kill_process(pid: u32) -> io::Result<()> {
+    let status = if cfg!(windows) {
+        Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/F"])
+            .stdout(Stdio::null())
+            .status()?
+    } else {
+        Command::new("kill").arg(pid.to_string()).status()?
+    };
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("failed to stop process {pid}")))
+    }
+}
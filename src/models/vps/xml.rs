@@ -5,6 +5,8 @@ use schemars::JsonSchema;
 
 use serde::{Deserialize, Serialize};
 
+use crate::models::vps::tunnel::TunnelSpec;
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct VpsConfigXml {
     pub vps: Vec<VpsEntryXml>,
@@ -25,4 +27,29 @@ pub struct VpsEntryXml {
     pub private_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub post_connect_script: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tag: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy_jump: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub local_forward: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remote_forward: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dynamic_forward: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forward_agent: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ssh_option: Vec<SshOptionXml>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tunnel: Vec<TunnelSpec>,
+}
+
+/// `<ssh_option name="ServerAliveInterval">30</ssh_option>`
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct SshOptionXml {
+    #[serde(rename = "@name")]
+    pub name: String,
+    #[serde(rename = "$text")]
+    pub value: String,
 }
@@ -0,0 +1,56 @@
+#[cfg(all(test, unix))]
+mod tests {
+    use tranquility::models::application::{ApplicationVersion, reports_version};
+
+    fn version(check: &str, expected: Option<&str>) -> ApplicationVersion {
+        ApplicationVersion {
+            name: "Default".into(),
+            check_command: Some(check.into()),
+            expected_version: expected.map(String::from),
+            dependencies: vec![],
+            install_methods: vec![],
+        }
+    }
+
+    #[test]
+    fn test_verify_missing_binary_fails() {
+        let result = version("tranquility-missing-binary --version", None).verify();
+        assert!(
+            result.is_err(),
+            "Expected missing binary to fail verification"
+        );
+    }
+
+    #[test]
+    fn test_verify_checks_expected_version() {
+        assert!(version("echo tool 1.2.3", Some("1.2.3")).verify().is_ok());
+        assert!(version("echo tool 1.2.3", Some("2.0.0")).verify().is_err());
+        assert!(version("echo tool 1.20", Some("1.2")).verify().is_err());
+    }
+
+    #[test]
+    fn test_reports_version_matches_whole_versions() {
+        assert!(reports_version("fish, version 3.7.1", "3.7.1"));
+        assert!(reports_version("tool v1.2 (build 5)", "1.2"));
+        assert!(reports_version(
+            "Docker version 27.3.1-1, build ce12230",
+            "27.3.1"
+        ));
+        assert!(reports_version("go version 1.22.", "1.22"));
+        assert!(!reports_version("tool 1.20", "1.2"));
+        assert!(!reports_version("tool 1.2.3", "1.2"));
+        assert!(!reports_version("tool 11.2", "1.2"));
+    }
+
+    #[test]
+    fn test_verify_fails_on_nonzero_exit() {
+        assert!(version("sh -c 'exit 3'", None).verify().is_err());
+        // A broken binary that still prints the version must not pass
+        assert!(
+            version("sh -c 'echo tool 1.2.3 >&2; exit 1'", Some("1.2.3"))
+                .verify()
+                .is_err()
+        );
+        assert!(version("true", None).verify().is_ok());
+    }
+}
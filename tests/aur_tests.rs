@@ -0,0 +1,69 @@
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{fs, path::Path, process::Command};
+
+    use tranquility::core::aur::AurBuilder;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .expect("git must be installed");
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    /// A local repository standing in for `<aur>/<pkg>.git`.
+    fn fake_aur(base: &Path, package: &str) {
+        let repo = base.join(format!("{package}.git"));
+        fs::create_dir_all(&repo).unwrap();
+        fs::write(repo.join("PKGBUILD"), format!("pkgname={package}\n")).unwrap();
+        git(&repo, &["init", "-q"]);
+        git(&repo, &["add", "PKGBUILD"]);
+        git(&repo, &["commit", "-q", "-m", "init"]);
+    }
+
+    #[test]
+    fn test_build_without_helper_runs_makepkg_in_checkout() {
+        let aur = tempfile::tempdir().unwrap();
+        let out = tempfile::tempdir().unwrap();
+        fake_aur(aur.path(), "hello");
+
+        let built = out.path().join("built");
+        let builder = AurBuilder::new()
+            .with_helper(None)
+            .with_base_url(&aur.path().display().to_string())
+            .with_makepkg(["sh", "-c", &format!("cp PKGBUILD {}", built.display())]);
+
+        builder.build("hello", false).unwrap();
+        assert_eq!(fs::read_to_string(built).unwrap(), "pkgname=hello\n");
+    }
+
+    #[test]
+    fn test_missing_package_fails() {
+        let aur = tempfile::tempdir().unwrap();
+        let builder = AurBuilder::new()
+            .with_helper(None)
+            .with_base_url(&aur.path().display().to_string())
+            .with_makepkg(["true"]);
+
+        assert!(builder.build("missing", false).is_err());
+    }
+
+    #[test]
+    fn test_helper_preferred() {
+        let builder = AurBuilder::new().with_helper(Some("paru"));
+        let cmd = builder.helper_command("visual-studio-code-bin").unwrap();
+        assert_eq!(
+            cmd.as_string(),
+            "paru -S visual-studio-code-bin --noconfirm --needed"
+        );
+        assert!(
+            AurBuilder::new()
+                .with_helper(None)
+                .helper_command("x")
+                .is_none()
+        );
+    }
+}
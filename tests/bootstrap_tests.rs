@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, fs};
+
+    use tranquility::core::bootstrap::{HOMEBREW, VerifyError, sha256_hex, verify_digest};
+
+    const ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(sha256_hex(b"abc"), ABC);
+    }
+
+    #[test]
+    fn test_unpinned_script_is_refused() {
+        let err = verify_digest("homebrew", ABC, None).unwrap_err();
+        assert!(matches!(err, VerifyError::NotPinned { .. }));
+        assert!(err.to_string().contains(ABC));
+    }
+
+    #[test]
+    fn test_pinned_digest_must_match() {
+        assert_eq!(verify_digest("nix", ABC, Some(&ABC.to_uppercase())), Ok(()));
+        assert!(matches!(
+            verify_digest("nix", ABC, Some("00")),
+            Err(VerifyError::Mismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_downloaded_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("install.sh");
+        fs::write(&script, "abc").unwrap();
+
+        let checksums = BTreeMap::from([("homebrew".to_string(), ABC.to_string())]);
+        let pinned = HOMEBREW.pinned_digest(&checksums);
+        assert_eq!(HOMEBREW.verify_file(&script, pinned).unwrap(), ABC);
+
+        fs::write(&script, "tampered").unwrap();
+        assert!(HOMEBREW.verify_file(&script, pinned).is_err());
+    }
+}
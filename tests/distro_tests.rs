@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use tranquility::models::{
+        distro::{BootstrapTool, Distro, DistroFamily},
+        package_manager::PackageManager,
+        system::parse_os_release,
+    };
+
+    fn detect(name: &str) -> Option<&'static Distro> {
+        let path = Path::new("tests/fixtures/os-release").join(name);
+        let content = fs::read_to_string(&path).expect("missing os-release fixture");
+        Distro::from_os_release(&parse_os_release(&content))
+    }
+
+    #[test]
+    fn test_os_release_corpus() {
+        use DistroFamily::*;
+        use PackageManager::*;
+
+        let cases = [
+            ("ubuntu-24.04", Debian, Apt),
+            ("debian-12", Debian, Apt),
+            ("linuxmint-21.3", Debian, Apt),
+            ("pop-22.04", Debian, Apt),
+            ("fedora-40", RedHat, Dnf),
+            ("rocky-9.4", RedHat, Dnf),
+            ("almalinux-9.4", RedHat, Dnf),
+            ("opensuse-tumbleweed", Suse, Zypper),
+            ("opensuse-leap-15.6", Suse, Zypper),
+            ("arch", Arch, Pacman),
+            ("endeavouros", Arch, Pacman),
+            ("alpine-3.20", Alpine, Apk),
+            ("gentoo", Gentoo, Portage),
+            ("void", Void, Xbps),
+            ("nixos-24.05", NixOS, Nix),
+        ];
+
+        for (file, family, pm) in cases {
+            let distro = detect(file).unwrap_or_else(|| panic!("{file} not detected"));
+            assert_eq!(distro.family, family, "{file}");
+            assert_eq!(distro.default_package_manager, pm, "{file}");
+            assert!(distro.package_managers.contains(&pm), "{file}");
+        }
+    }
+
+    #[test]
+    fn test_derivatives_use_id_like() {
+        assert_eq!(detect("linuxmint-21.3").unwrap().id, "ubuntu");
+        assert_eq!(detect("rocky-9.4").unwrap().id, "rhel");
+        assert_eq!(detect("unknown"), None);
+    }
+
+    #[test]
+    fn test_bootstrap_packages() {
+        assert_eq!(
+            DistroFamily::Debian.bootstrap_package(BootstrapTool::Snap),
+            Some("snapd")
+        );
+        assert_eq!(
+            DistroFamily::Gentoo.bootstrap_package(BootstrapTool::Flatpak),
+            Some("sys-apps/flatpak")
+        );
+        assert_eq!(
+            DistroFamily::Arch.bootstrap_package(BootstrapTool::Snap),
+            None
+        );
+    }
+}
@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use tranquility::models::{
+        package_manager::PackageManager,
+        vps::facts::{DiskUsage, FACTS_SCRIPT, RemoteFacts},
+    };
+
+    const UBUNTU: &str = "### os-release
+PRETTY_NAME=\"Ubuntu 24.04.1 LTS\"
+NAME=\"Ubuntu\"
+VERSION_ID=\"24.04\"
+ID=ubuntu
+ID_LIKE=debian
+### kernel
+Linux 6.8.0-45-generic
+### arch
+x86_64
+### uptime
+93784.52 180000.10
+### cpu
+4
+model name	: AMD EPYC 7543 32-Core Processor
+### memory
+MemTotal:        8126484 kB
+MemAvailable:    6021112 kB
+### disk
+Filesystem     1024-blocks     Used Available Capacity Mounted on
+tmpfs               812652     1104    811548       1% /run
+/dev/sda1         81106868 12345678  68744806      16% /
+/dev/sdb1         10255636   102400  10153236       1% /mnt/backup disk
+### package-managers
+apt
+snap
+";
+
+    #[test]
+    fn test_parse_facts() {
+        let facts = RemoteFacts::parse("web-1", UBUNTU);
+        assert_eq!(facts.vps_id, "web-1");
+        assert_eq!(facts.os.as_deref(), Some("Ubuntu 24.04.1 LTS"));
+        assert_eq!(facts.distro.as_deref(), Some("ubuntu"));
+        assert_eq!(facts.distro_like, ["debian"]);
+        assert_eq!(facts.version.as_deref(), Some("24.04"));
+        assert_eq!(facts.kernel.as_deref(), Some("Linux 6.8.0-45-generic"));
+        assert_eq!(facts.arch.as_deref(), Some("x86_64"));
+        assert_eq!(facts.uptime_secs, Some(93784));
+        assert_eq!(facts.cpu_count, Some(4));
+        assert_eq!(
+            facts.cpu_brand.as_deref(),
+            Some("AMD EPYC 7543 32-Core Processor")
+        );
+        assert_eq!(facts.memory_total_kb, Some(8126484));
+        assert_eq!(facts.memory_available_kb, Some(6021112));
+        assert_eq!(
+            facts.available_package_managers,
+            [PackageManager::Apt, PackageManager::Snap]
+        );
+        assert_eq!(facts.default_package_manager, Some(PackageManager::Apt));
+        assert_eq!(facts.distro_info().map(|d| d.id), Some("ubuntu"));
+    }
+
+    #[test]
+    fn test_parse_disks_skips_pseudo_filesystems() {
+        let facts = RemoteFacts::parse("web-1", UBUNTU);
+        assert_eq!(
+            facts.disks,
+            [
+                DiskUsage {
+                    filesystem: "/dev/sda1".into(),
+                    mount: "/".into(),
+                    total_kb: 81106868,
+                    used_kb: 12345678,
+                    available_kb: 68744806,
+                },
+                DiskUsage {
+                    filesystem: "/dev/sdb1".into(),
+                    mount: "/mnt/backup disk".into(),
+                    total_kb: 10255636,
+                    used_kb: 102400,
+                    available_kb: 10153236,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_sparse_output() {
+        // A minimal system without /proc or os-release
+        let facts = RemoteFacts::parse(
+            "tiny",
+            "### os-release\n### kernel\nLinux 5.10\n### uptime\n### package-managers\napk\n",
+        );
+        assert_eq!(facts.kernel.as_deref(), Some("Linux 5.10"));
+        assert_eq!(facts.os, None);
+        assert_eq!(facts.uptime_secs, None);
+        assert!(facts.disks.is_empty());
+        // Without a known distro, the first manager found is the default
+        assert_eq!(facts.default_package_manager, Some(PackageManager::Apk));
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let facts = RemoteFacts::parse("web-1", UBUNTU);
+        facts.save(dir.path()).unwrap();
+
+        assert_eq!(RemoteFacts::load(dir.path(), "web-1"), Some(facts));
+        assert_eq!(RemoteFacts::load(dir.path(), "db-1"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_facts_script_runs_locally() {
+        let output = std::process::Command::new("sh")
+            .args(["-c", FACTS_SCRIPT])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+
+        let facts = RemoteFacts::parse("local", &String::from_utf8_lossy(&output.stdout));
+        assert!(facts.kernel.unwrap().starts_with("Linux"));
+        assert_eq!(facts.arch.as_deref(), Some(std::env::consts::ARCH));
+        assert!(facts.memory_total_kb.is_some());
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+        time::Duration,
+    };
+
+    use tranquility::models::vps::{
+        fleet::{HostResult, Selector, run_parallel},
+        json::{VpsConfig, VpsEntry},
+    };
+
+    fn entry(id: &str, tags: &[&str]) -> VpsEntry {
+        VpsEntry {
+            id: Some(id.into()),
+            name: Some(id.into()),
+            host: format!("{id}.example.com"),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..VpsEntry::default()
+        }
+    }
+
+    fn fleet() -> Vec<VpsEntry> {
+        vec![
+            entry("web-1", &["web", "prod"]),
+            entry("db-1", &["db", "prod"]),
+            entry("web-2", &["web"]),
+            entry("bastion", &[]),
+        ]
+    }
+
+    fn ids(selected: Vec<&VpsEntry>) -> Vec<&str> {
+        selected.iter().map(|v| v.effective_id()).collect()
+    }
+
+    #[test]
+    fn test_select_by_tag_and_id() {
+        let entries = fleet();
+        let selector = Selector {
+            tags: vec!["web".into()],
+            ..Selector::default()
+        };
+        assert_eq!(ids(selector.select(&entries).unwrap()), ["web-1", "web-2"]);
+
+        // Ids and tags combine, in config order
+        let selector = Selector {
+            ids: vec!["bastion".into()],
+            tags: vec!["db".into()],
+            ..Selector::default()
+        };
+        assert_eq!(ids(selector.select(&entries).unwrap()), ["db-1", "bastion"]);
+
+        let all = Selector {
+            all: true,
+            ..Selector::default()
+        };
+        assert_eq!(all.select(&entries).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_select_errors() {
+        let entries = fleet();
+        let unknown = Selector {
+            ids: vec!["web-1".into(), "nope".into()],
+            ..Selector::default()
+        };
+        assert!(
+            unknown
+                .select(&entries)
+                .unwrap_err()
+                .to_string()
+                .contains("nope")
+        );
+
+        let untagged = Selector {
+            tags: vec!["staging".into()],
+            ..Selector::default()
+        };
+        assert!(untagged.select(&entries).is_err());
+    }
+
+    #[test]
+    fn test_is_fleet() {
+        assert!(!Selector::default().is_fleet());
+        let single = Selector {
+            ids: vec!["web-1".into()],
+            ..Selector::default()
+        };
+        assert!(!single.is_fleet());
+        let tagged = Selector {
+            tags: vec!["web".into()],
+            ..Selector::default()
+        };
+        assert!(tagged.is_fleet());
+    }
+
+    #[test]
+    fn test_run_parallel_keeps_order_and_limit() {
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let items: Vec<u64> = (0..10).collect();
+
+        let results = run_parallel(&items, 3, |&n| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            // Later items finish first
+            thread::sleep(Duration::from_millis(30 - n * 2));
+            running.fetch_sub(1, Ordering::SeqCst);
+            n * 10
+        });
+
+        assert_eq!(results, (0..10).map(|n| n * 10).collect::<Vec<_>>());
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+        assert!(peak.load(Ordering::SeqCst) > 1);
+        assert!(run_parallel(&Vec::<u8>::new(), 4, |_| ()).is_empty());
+    }
+
+    #[test]
+    fn test_host_result_success() {
+        let result = |code, error: Option<&str>| HostResult {
+            id: "web-1".into(),
+            code,
+            duration: Duration::ZERO,
+            error: error.map(String::from),
+        };
+        assert!(result(Some(0), None).success());
+        assert!(!result(Some(2), None).success());
+        assert!(!result(None, Some("timed out after 5s")).success());
+    }
+
+    #[test]
+    fn test_tags_survive_xml() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vps.xml");
+        VpsConfig { vps: fleet() }.save_to_file(&path).unwrap();
+
+        let loaded = VpsConfig::load_from_file(&path).unwrap();
+        assert_eq!(loaded.vps[0].tags, ["web", "prod"]);
+        assert!(loaded.vps[3].tags.is_empty());
+    }
+}
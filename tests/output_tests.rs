@@ -0,0 +1,111 @@
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{
+        process::{Command, Stdio},
+        time::{Duration, Instant},
+    };
+
+    use tranquility::core::shell::{
+        ShellCommand,
+        output::{Stream, stream_child},
+    };
+
+    fn spawn(script: &str) -> std::process::Child {
+        Command::new("sh")
+            .args(["-c", script])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_stream_captures_both_streams() {
+        let mut child = spawn("echo one; echo two >&2; echo three");
+        let (status, output) = stream_child(&mut child, "sh", None, false);
+
+        assert!(status.unwrap().success());
+        let stdout: Vec<&str> = output
+            .lines
+            .iter()
+            .filter(|(s, _)| *s == Stream::Stdout)
+            .map(|(_, l)| l.as_str())
+            .collect();
+        assert_eq!(stdout, ["one", "three"]);
+        assert!(output.lines.contains(&(Stream::Stderr, "two".to_string())));
+        assert_eq!(output.text(Stream::Stdout), "one\nthree");
+    }
+
+    #[test]
+    fn test_capture_times_out() {
+        let err = ShellCommand::new("sh")
+            .with_args(["-c", "sleep 30"])
+            .with_timeout(Some(Duration::from_millis(300)))
+            .capture()
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn test_stream_keeps_output_of_failed_command() {
+        let mut child = spawn("echo partial; exit 3");
+        let (status, output) = stream_child(&mut child, "sh", None, false);
+
+        assert_eq!(status.unwrap().code(), Some(3));
+        assert_eq!(output.tail(5), "partial");
+    }
+
+    #[test]
+    fn test_stream_kills_on_timeout() {
+        let mut child = spawn("echo started; sleep 30");
+        let start = Instant::now();
+        let (status, output) =
+            stream_child(&mut child, "sh", Some(Duration::from_millis(300)), false);
+
+        let err = status.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert_eq!(output.tail(1), "started");
+    }
+
+    #[test]
+    fn test_stream_times_out_while_output_keeps_arriving() {
+        let mut child = spawn("while :; do echo x; done");
+        let start = Instant::now();
+        let (status, output) = stream_child(&mut child, "sh", Some(Duration::from_secs(1)), false);
+
+        let err = status.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+        assert!(start.elapsed() < Duration::from_secs(3));
+        assert_eq!(output.tail(1), "x");
+    }
+
+    #[test]
+    fn test_tail_keeps_last_lines() {
+        let mut child = spawn("for i in 1 2 3 4 5; do echo $i; done");
+        let (_, output) = stream_child(&mut child, "sh", None, false);
+        assert_eq!(output.tail(2), "4\n5");
+    }
+
+    #[test]
+    fn test_run_verbose_reports_failure_and_timeout() {
+        assert!(
+            ShellCommand::new("sh")
+                .with_args(["-c", "echo warning >&2"])
+                .run_verbose(false)
+                .is_ok()
+        );
+        assert!(
+            ShellCommand::new("sh")
+                .with_args(["-c", "exit 1"])
+                .run_verbose(false)
+                .is_err()
+        );
+        let err = ShellCommand::new("sleep")
+            .with_args(["30"])
+            .with_timeout(Some(Duration::from_millis(300)))
+            .run_verbose(false)
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+    }
+}
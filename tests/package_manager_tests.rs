@@ -0,0 +1,209 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use tranquility::models::{
+        package_manager::{
+            PackageManager, PackageRequest, parse_installed, parse_installed_version,
+            version_matches,
+        },
+        package_options::{FlatpakOptions, PackageOptions, SnapOptions},
+    };
+
+    fn set(items: &[&str]) -> HashSet<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_dpkg_query() {
+        let output = "build-essential ii \nfonts-firacode rc \ncurl ii \n";
+        let found = parse_installed(
+            PackageManager::Apt,
+            output,
+            &["build-essential", "fonts-firacode", "curl", "git"],
+        );
+        assert_eq!(found, set(&["build-essential", "curl"]));
+    }
+
+    #[test]
+    fn test_parse_rpm_and_pacman() {
+        let rpm = "git\npackage fish is not installed\n";
+        assert_eq!(
+            parse_installed(PackageManager::Dnf, rpm, &["git", "fish"]),
+            set(&["git"])
+        );
+
+        let pacman = "base-devel 1-2\nzsh 5.9-5\n";
+        assert_eq!(
+            parse_installed(PackageManager::Pacman, pacman, &["zsh", "fish"]),
+            set(&["zsh"])
+        );
+    }
+
+    #[test]
+    fn test_parse_list_style_managers() {
+        let snap = "Name    Version  Rev  Tracking  Publisher  Notes\ncode    1.95     170  stable    vscode*    classic\ncore22  2024     1663 stable    canonical  base\n";
+        assert_eq!(
+            parse_installed(PackageManager::Snap, snap, &["code", "kubectl"]),
+            set(&["code"])
+        );
+
+        let brew = "git\nhashicorp/tap/terraform\nvisual-studio-code\n";
+        assert_eq!(
+            parse_installed(
+                PackageManager::Brew,
+                brew,
+                &["terraform", "visual-studio-code", "fish"]
+            ),
+            set(&["terraform", "visual-studio-code"])
+        );
+
+        let choco = "Firefox|128.0\ngit|2.45.2\n";
+        assert_eq!(
+            parse_installed(PackageManager::Choco, choco, &["firefox", "vlc"]),
+            set(&["firefox"])
+        );
+    }
+
+    #[test]
+    fn test_parse_nix() {
+        let nix_env = "ripgrep-14.1.0\npython3-3.11.9\nhello-2.12.1\n";
+        assert_eq!(
+            parse_installed(PackageManager::Nix, nix_env, &["ripgrep", "python3", "fd"]),
+            set(&["ripgrep", "python3"])
+        );
+
+        let profile = r#"{"elements":{"ripgrep":{"active":true,"attrPath":"legacyPackages.x86_64-linux.ripgrep"}},"version":3}"#;
+        assert_eq!(
+            parse_installed(PackageManager::Nix, profile, &["ripgrep", "fd"]),
+            set(&["ripgrep"])
+        );
+
+        let legacy =
+            r#"{"elements":[{"attrPath":"legacyPackages.aarch64-darwin.fd"}],"version":2}"#;
+        assert_eq!(
+            parse_installed(PackageManager::Nix, legacy, &["ripgrep", "fd"]),
+            set(&["fd"])
+        );
+    }
+
+    #[test]
+    fn test_versioned_install_commands() {
+        let request = PackageRequest::new("nodejs").with_version(Some("20.11.1"));
+        let cmd = |pm: PackageManager| pm.install_command(&request).unwrap().as_string();
+
+        assert!(
+            cmd(PackageManager::Apt).ends_with("apt install nodejs=20.11.1 --allow-downgrades -y")
+        );
+        assert!(cmd(PackageManager::Dnf).ends_with("dnf install nodejs-20.11.1 -y"));
+        assert!(
+            cmd(PackageManager::Zypper).ends_with("zypper install --oldpackage -y nodejs=20.11.1")
+        );
+        assert_eq!(cmd(PackageManager::Brew), "brew install nodejs@20.11.1");
+        assert_eq!(
+            cmd(PackageManager::Choco),
+            "choco install nodejs -y --version 20.11.1 --allow-downgrade"
+        );
+        assert!(PackageManager::Pacman.install_command(&request).is_err());
+    }
+
+    #[test]
+    fn test_upgrade_commands() {
+        let request = PackageRequest::new("ripgrep");
+        let cmd = |pm: PackageManager| pm.upgrade_command(&request).unwrap().as_string();
+
+        assert!(cmd(PackageManager::Apt).ends_with("apt install --only-upgrade ripgrep -y"));
+        assert!(cmd(PackageManager::Dnf).ends_with("dnf upgrade ripgrep -y"));
+        assert_eq!(cmd(PackageManager::Brew), "brew upgrade ripgrep");
+    }
+
+    #[test]
+    fn test_parse_installed_version() {
+        let parse = |pm, output: &str, pkg| parse_installed_version(pm, output, pkg);
+        assert_eq!(
+            parse(
+                PackageManager::Apt,
+                "5:27.3.1-1~ubuntu.24.04~noble",
+                "docker-ce"
+            )
+            .as_deref(),
+            Some("5:27.3.1-1~ubuntu.24.04~noble")
+        );
+        assert_eq!(
+            parse(
+                PackageManager::Apk,
+                "nodejs-20.15.1-r0 x86_64 {nodejs} (MIT) [installed]\n",
+                "nodejs"
+            )
+            .as_deref(),
+            Some("20.15.1-r0")
+        );
+        assert_eq!(
+            parse(
+                PackageManager::Portage,
+                "dev-vcs/git-2.45.2\n",
+                "dev-vcs/git"
+            )
+            .as_deref(),
+            Some("2.45.2")
+        );
+        assert_eq!(
+            parse(PackageManager::Choco, "nodejs|20.11.1\n", "nodejs").as_deref(),
+            Some("20.11.1")
+        );
+        assert_eq!(parse(PackageManager::Apt, "", "missing"), None);
+    }
+
+    #[test]
+    fn test_version_matches_pins() {
+        assert!(version_matches("5:27.3.1-1~ubuntu", "5:27.3.1-1~ubuntu"));
+        assert!(version_matches("5:27.3.1-1~ubuntu", "27.3.1"));
+        assert!(version_matches("20.11.1-1.fc40", "20.11.1"));
+        assert!(!version_matches("20.11.10-1.fc40", "20.11.1"));
+        assert!(!version_matches("27.3.1-1", "26.1.4"));
+        assert!(!version_matches("1:27.3.1-1", "5:27.3.1-1"));
+    }
+
+    #[test]
+    fn test_holds_only_for_pinned_packages() {
+        let pinned = PackageRequest::new("docker-ce").with_version(Some("5:27.3.1-1"));
+        let hold = PackageManager::Apt.hold_command(&pinned).unwrap();
+        assert!(hold.as_string().ends_with("apt-mark hold docker-ce"));
+
+        let unhold = PackageManager::Dnf.unhold_command(&pinned).unwrap();
+        assert!(
+            unhold
+                .as_string()
+                .ends_with("dnf versionlock delete docker-ce")
+        );
+
+        let unpinned = PackageRequest::new("docker-ce");
+        assert!(PackageManager::Apt.hold_command(&unpinned).is_none());
+        assert!(PackageManager::Apk.hold_command(&pinned).is_none());
+    }
+
+    #[test]
+    fn test_install_options() {
+        let options = PackageOptions {
+            flatpak: Some(FlatpakOptions {
+                remote: Some("fedora".into()),
+                user: true,
+            }),
+            snap: Some(SnapOptions {
+                classic: true,
+                channel: Some("1.30/stable".into()),
+            }),
+            ..PackageOptions::default()
+        };
+        let request = PackageRequest::new("code").with_options(Some(&options));
+
+        let flatpak = PackageManager::Flatpak.install_command(&request).unwrap();
+        assert_eq!(flatpak.as_string(), "flatpak install -y --user fedora code");
+
+        let snap = PackageManager::Snap.install_command(&request).unwrap();
+        assert!(
+            snap.as_string()
+                .ends_with("snap install code --classic --channel=1.30/stable")
+        );
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use tranquility::core::shell::privilege::Escalation;
+
+    fn args(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_root_never_escalates() {
+        assert_eq!(Escalation::Auto.resolve(true, |_| true), Escalation::None);
+        assert_eq!(Escalation::Doas.resolve(true, |_| true), Escalation::None);
+    }
+
+    #[test]
+    fn test_auto_detects_installed_tool() {
+        assert_eq!(
+            Escalation::Auto.resolve(false, |p| p == "doas"),
+            Escalation::Doas
+        );
+        assert_eq!(Escalation::Auto.resolve(false, |_| true), Escalation::Sudo);
+        assert_eq!(Escalation::Auto.resolve(false, |_| false), Escalation::None);
+        assert_eq!(Escalation::Run0.resolve(false, |_| false), Escalation::Run0);
+    }
+
+    #[test]
+    fn test_wrap_keeps_environment() {
+        let env = vec![("DEBIAN_FRONTEND".to_string(), "noninteractive".to_string())];
+        let apt = args(&["install", "-y", "git"]);
+
+        let (program, wrapped) = Escalation::Sudo.wrap("apt", &apt, &env);
+        assert_eq!(program, "sudo");
+        assert_eq!(
+            wrapped,
+            args(&[
+                "--preserve-env=DEBIAN_FRONTEND",
+                "apt",
+                "install",
+                "-y",
+                "git"
+            ])
+        );
+
+        let (program, wrapped) = Escalation::Doas.wrap("apt", &apt, &env);
+        assert_eq!(program, "doas");
+        assert_eq!(
+            wrapped,
+            args(&[
+                "env",
+                "DEBIAN_FRONTEND=noninteractive",
+                "apt",
+                "install",
+                "-y",
+                "git"
+            ])
+        );
+
+        let (program, wrapped) = Escalation::None.wrap("apt", &apt, &env);
+        assert_eq!(program, "apt");
+        assert_eq!(wrapped, apt);
+    }
+
+    #[test]
+    fn test_config_names() {
+        let parsed: Escalation = serde_json::from_str("\"run0\"").unwrap();
+        assert_eq!(parsed, Escalation::Run0);
+        assert_eq!(Escalation::Pkexec.to_string(), "pkexec");
+    }
+}
@@ -0,0 +1,248 @@
+#[cfg(test)]
+mod tests {
+    use os_info::Type as OSType;
+    use serde_json::json;
+    use tranquility::models::{
+        application::{Application, plan_steps, step::StepDecision},
+        category::Category,
+        package_manager::PackageManager,
+        vps::{
+            facts::RemoteFacts,
+            provision::{
+                install_script, no_method_reason, presence_script, remote_os_type, select_remote,
+                server_apps,
+            },
+        },
+    };
+
+    fn facts(distro: &str, like: &[&str], managers: &[PackageManager]) -> RemoteFacts {
+        RemoteFacts {
+            vps_id: "web".into(),
+            distro: Some(distro.into()),
+            distro_like: like.iter().map(|s| s.to_string()).collect(),
+            arch: Some("x86_64".into()),
+            available_package_managers: managers.to_vec(),
+            ..RemoteFacts::default()
+        }
+    }
+
+    fn app(value: serde_json::Value) -> Application {
+        serde_json::from_value(value).expect("valid application")
+    }
+
+    fn fish() -> Application {
+        app(json!({
+            "id": "fish-shell",
+            "name": "Fish Shell",
+            "server_compatible": true,
+            "categories": ["Shells"],
+            "supported_systems": ["MacLin"],
+            "versions": [{
+                "name": "Default",
+                "check_command": "fish --version",
+                "install_methods": [
+                    { "os": [{ "os_type": "Fedora" }], "package_manager": "Dnf", "package_name": "fish" },
+                    { "os": [{ "os_type": "Linux" }], "package_manager": "Apt", "package_name": "fish" }
+                ]
+            }]
+        }))
+    }
+
+    #[test]
+    fn test_remote_os_type_falls_back_to_id_like() {
+        assert_eq!(
+            remote_os_type(&facts("ubuntu", &["debian"], &[])),
+            Some(OSType::Ubuntu)
+        );
+        assert_eq!(
+            remote_os_type(&facts("opensuse-tumbleweed", &[], &[])),
+            Some(OSType::openSUSE)
+        );
+        assert_eq!(
+            remote_os_type(&facts("someos", &["rhel", "fedora"], &[])),
+            Some(OSType::RedHatEnterprise)
+        );
+        assert_eq!(remote_os_type(&facts("someos", &[], &[])), None);
+    }
+
+    #[test]
+    fn test_select_remote_prefers_distro_and_available_managers() {
+        let fish = fish();
+        let fedora = facts("fedora", &[], &[PackageManager::Dnf]);
+        let selection = select_remote(&fish, &fedora, OSType::Fedora).unwrap();
+        assert_eq!(selection.method.package_manager, Some(PackageManager::Dnf));
+
+        // The generic Linux method needs apt, which Fedora lacks
+        let no_dnf = facts("fedora", &[], &[]);
+        assert!(select_remote(&fish, &no_dnf, OSType::Fedora).is_none());
+
+        let ubuntu = facts("ubuntu", &["debian"], &[PackageManager::Apt]);
+        let selection = select_remote(&fish, &ubuntu, OSType::Ubuntu).unwrap();
+        assert_eq!(selection.method.package_manager, Some(PackageManager::Apt));
+    }
+
+    #[test]
+    fn test_install_script_for_package() {
+        let fish = fish();
+        let ubuntu = facts("ubuntu", &["debian"], &[PackageManager::Apt]);
+        let selection = select_remote(&fish, &ubuntu, OSType::Ubuntu).unwrap();
+
+        let install = install_script(&selection, false).unwrap();
+        assert_eq!(
+            install.script,
+            "set -e\n\
+             sudo apt-get update\n\
+             sudo apt install fish -y\n\
+             command -v fish >/dev/null 2>&1 || { echo '`fish` was not found on the PATH' >&2; exit 1; }"
+        );
+        assert!(install.uses_sudo);
+
+        let install = install_script(&selection, true).unwrap();
+        assert!(install.script.contains("\napt install fish -y\n"));
+        assert!(!install.uses_sudo);
+    }
+
+    #[test]
+    fn test_install_script_for_steps() {
+        let tool = app(json!({
+            "name": "Tool",
+            "server_compatible": true,
+            "categories": ["Servers"],
+            "supported_systems": ["Linux"],
+            "versions": [{
+                "name": "1.2",
+                "install_methods": [{
+                    "os": [{ "os_type": "Linux" }],
+                    "steps": {
+                        "install": [
+                            "curl -fsSL https://example.com/{{version}}/{{arch}} -o /tmp/tool",
+                            {
+                                "run": "install /tmp/tool /usr/local/bin/tool",
+                                "sudo": true,
+                                "creates": "/usr/local/bin/tool",
+                                "unless": "command -v tool",
+                                "env": { "PREFIX": "{{home}}/.local" },
+                                "cwd": "~/build"
+                            }
+                        ]
+                    }
+                }]
+            }]
+        }));
+        let debian = facts("debian", &[], &[PackageManager::Apt]);
+        let selection = select_remote(&tool, &debian, OSType::Debian).unwrap();
+
+        let install = install_script(&selection, false).unwrap();
+        let lines: Vec<&str> = install.script.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "set -e",
+                "sh -c 'curl -fsSL https://example.com/1.2/x86_64 -o /tmp/tool'",
+                "if [ ! -e /usr/local/bin/tool ] && \
+                 ! ( cd \"$HOME\"/build && env PREFIX=\"$HOME\"/.local sh -c 'command -v tool' ); then \
+                 ( cd \"$HOME\"/build && sudo env PREFIX=\"$HOME\"/.local sh -c \
+                 'install /tmp/tool /usr/local/bin/tool' ); fi",
+            ]
+        );
+
+        // The dry-run plan can't test remote paths, so it reports them
+        let steps = selection.method.steps.as_ref().unwrap();
+        let plan = plan_steps(&selection.context, &steps.install_phases());
+        assert_eq!(plan[0].decision, StepDecision::Run);
+        assert_eq!(
+            plan[1].decision,
+            StepDecision::Check("creates `/usr/local/bin/tool`, unless `command -v tool`".into())
+        );
+    }
+
+    #[test]
+    fn test_presence_checks_expected_and_pinned_versions() {
+        let docker = app(json!({
+            "name": "Docker",
+            "server_compatible": true,
+            "categories": ["Servers"],
+            "supported_systems": ["Linux"],
+            "versions": [{
+                "name": "27",
+                "check_command": "docker --version",
+                "expected_version": "27.3.1",
+                "install_methods": [{
+                    "os": [{ "os_type": "Linux" }],
+                    "package_manager": "Apt",
+                    "package_name": "docker-ce",
+                    "package_version": "27.3.1"
+                }]
+            }]
+        }));
+        let debian = facts("debian", &[], &[PackageManager::Apt]);
+        let selection = select_remote(&docker, &debian, OSType::Debian).unwrap();
+
+        let check = presence_script(&selection).unwrap();
+        assert_eq!(
+            check.script,
+            "command -v docker >/dev/null 2>&1 && \
+             docker --version 2>&1 | grep -qF -- 27.3.1 && \
+             dpkg-query -W '-f=${Version}' docker-ce"
+        );
+        assert!(check.matches_pin("5:27.3.1-1~debian.12~bookworm"));
+        assert!(!check.matches_pin("5:26.1.0-1~debian.12~bookworm"));
+
+        let fish = fish();
+        let selection = select_remote(&fish, &debian, OSType::Debian).unwrap();
+        let check = presence_script(&selection).unwrap();
+        assert_eq!(check.script, "command -v fish >/dev/null 2>&1");
+        assert!(check.matches_pin(""));
+    }
+
+    #[test]
+    fn test_no_method_reason_names_vendor_repositories() {
+        let docker = app(json!({
+            "name": "Docker",
+            "server_compatible": true,
+            "categories": ["Servers"],
+            "supported_systems": ["Linux"],
+            "versions": [{
+                "name": "Default",
+                "install_methods": [{
+                    "os": [{ "os_type": "Linux" }],
+                    "package_manager": "Apt",
+                    "package_name": "docker-ce",
+                    "repository": { "name": "docker" }
+                }]
+            }]
+        }));
+        let debian = facts("debian", &[], &[PackageManager::Apt]);
+        assert!(select_remote(&docker, &debian, OSType::Debian).is_none());
+        assert_eq!(
+            no_method_reason(&docker, &debian, OSType::Debian),
+            "vendor repository not supported remotely"
+        );
+
+        let fedora = facts("fedora", &[], &[PackageManager::Dnf]);
+        assert_eq!(
+            no_method_reason(&docker, &fedora, OSType::Fedora),
+            "no install method for Fedora"
+        );
+    }
+
+    #[test]
+    fn test_server_apps_filters_by_name_and_category() {
+        let apps = vec![
+            fish(),
+            app(json!({
+                "name": "Alacritty",
+                "server_compatible": false,
+                "categories": ["TerminalEmulators"],
+                "supported_systems": ["MacLin"],
+                "versions": []
+            })),
+        ];
+
+        assert_eq!(server_apps(&apps, &[], &[]).len(), 1);
+        assert_eq!(server_apps(&apps, &["FISH-SHELL".into()], &[]).len(), 1);
+        assert_eq!(server_apps(&apps, &["Alacritty".into()], &[]).len(), 0);
+        assert_eq!(server_apps(&apps, &[], &[Category::Servers]).len(), 0);
+        assert_eq!(server_apps(&apps, &[], &[Category::Shells]).len(), 1);
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use tranquility::core::shell::{
+        ShellCommand,
+        quote::{
+            join_posix, join_powershell, quote_posix, quote_powershell, split_posix,
+            split_powershell,
+        },
+    };
+
+    #[test]
+    fn test_plain_words_stay_unquoted() {
+        assert_eq!(quote_posix("install"), "install");
+        assert_eq!(quote_posix("--version=1.2"), "--version=1.2");
+        assert_eq!(quote_powershell("C:\\tools\\bin"), "C:\\tools\\bin");
+    }
+
+    #[test]
+    fn test_quotes_special_arguments() {
+        assert_eq!(quote_posix(""), "''");
+        assert_eq!(quote_posix("it's"), r"'it'\''s'");
+        assert_eq!(quote_posix("a b"), "'a b'");
+        assert_eq!(quote_powershell("it's"), "'it''s'");
+        assert_eq!(quote_powershell("-Force"), "-Force");
+        assert_eq!(quote_powershell("a,b"), "'a,b'");
+    }
+
+    #[test]
+    fn test_split_posix_handles_shell_forms() {
+        assert_eq!(
+            split_posix(r#"echo "a \"b\"" c\ d 'e f'"#).unwrap(),
+            ["echo", "a \"b\"", "c d", "e f"]
+        );
+        assert!(split_posix("'open").is_none());
+    }
+
+    #[test]
+    fn test_remote_script_survives_single_quotes() {
+        let script = "echo 'hello world' && test \"$HOME\" != ''";
+        let cmd = ShellCommand::from_remote_script("deploy@example.com", &[], script, true);
+
+        assert_eq!(cmd.command, "ssh");
+        assert_eq!(cmd.args[..2], ["--", "deploy@example.com"]);
+        assert_eq!(
+            split_posix(&cmd.args[2]).unwrap(),
+            ["sudo", "sh", "-c", script]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_as_string_runs_in_sh() {
+        let cmd = ShellCommand::new("printf").with_args(["%s|", "a b", "it's", "$HOME", ""]);
+        let out = std::process::Command::new("sh")
+            .args(["-c", &cmd.as_string()])
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "a b|it's|$HOME||");
+    }
+
+    proptest! {
+        #[test]
+        fn prop_posix_round_trip(args in prop::collection::vec(any::<String>(), 0..6)) {
+            prop_assert_eq!(split_posix(&join_posix(&args)).unwrap(), args);
+        }
+
+        #[test]
+        fn prop_powershell_round_trip(args in prop::collection::vec(any::<String>(), 0..6)) {
+            prop_assert_eq!(split_powershell(&join_powershell(&args)).unwrap(), args);
+        }
+    }
+
+    #[cfg(unix)]
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn prop_posix_quoting_survives_sh(args in prop::collection::vec("[^\0]*", 1..4)) {
+            let line = format!("printf '%s\\0' {}", join_posix(&args));
+            let out = std::process::Command::new("sh").args(["-c", &line]).output().unwrap();
+            let mut words: Vec<String> = String::from_utf8_lossy(&out.stdout)
+                .split('\0')
+                .map(str::to_string)
+                .collect();
+            words.pop();
+            prop_assert_eq!(words, args);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use std::{fs, time::Duration};
+
+    use tranquility::core::report::{InstallReport, ReportStatus};
+
+    #[test]
+    fn test_report_json_and_failures() {
+        let mut report = InstallReport::new("install", "local", false);
+        report.record(
+            "Fish Shell",
+            ReportStatus::Installed,
+            Some(Duration::from_millis(1500)),
+            None,
+        );
+        report.record("Alacritty", ReportStatus::Failed, None, Some("boom".into()));
+        report.record("ZSH Shell", ReportStatus::Skipped, None, None);
+        assert!(report.has_failures());
+        assert_eq!(report.count(ReportStatus::Skipped), 1);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = report.write_json(dir.path()).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+
+        assert_eq!(json["target"], "local");
+        assert_eq!(json["entries"][0]["status"], "installed");
+        assert_eq!(json["entries"][0]["duration_secs"], 1.5);
+        assert_eq!(json["entries"][1]["status"], "failed");
+        assert_eq!(json["entries"][1]["message"], "boom");
+    }
+
+    #[test]
+    fn test_missing_method_counts_as_failure() {
+        let mut report = InstallReport::new("install", "local", false);
+        report.record("ZSH Shell", ReportStatus::Skipped, None, None);
+        assert!(!report.has_failures());
+
+        report.record("Alacritty", ReportStatus::NoMethod, None, None);
+        assert!(report.has_failures());
+    }
+
+    #[test]
+    fn test_uninstall_and_upgrade_statuses() {
+        let mut report = InstallReport::new("uninstall", "local", false);
+        report.record("Fish Shell", ReportStatus::Uninstalled, None, None);
+        report.record("ZSH Shell", ReportStatus::Upgraded, None, None);
+        assert!(!report.has_failures());
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["entries"][0]["status"], "uninstalled");
+        assert_eq!(json["entries"][1]["status"], "upgraded");
+
+        report.record("Alacritty", ReportStatus::Failed, None, Some("boom".into()));
+        assert!(report.has_failures());
+    }
+}
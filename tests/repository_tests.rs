@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use tranquility::{
+        core::template::TemplateContext,
+        models::{
+            repository::{Repository, parse_fingerprints, same_fingerprint},
+            system::parse_os_release,
+        },
+    };
+
+    fn docker() -> Repository {
+        Repository {
+            name: "docker".into(),
+            key_url: Some("https://download.docker.com/linux/ubuntu/gpg".into()),
+            url: Some("https://download.docker.com/linux/{{distro}}".into()),
+            suite: Some("jammy".into()),
+            components: vec!["stable".into()],
+            ..Repository::default()
+        }
+    }
+
+    #[test]
+    fn test_apt_source_line() {
+        let ctx = TemplateContext::new()
+            .with_var("arch", "x86_64")
+            .with_var("distro", "ubuntu");
+        let line = docker().apt_source(&ctx).unwrap();
+        assert_eq!(
+            line,
+            "deb [arch=amd64 signed-by=/etc/apt/keyrings/docker.gpg] \
+             https://download.docker.com/linux/ubuntu jammy stable\n"
+        );
+    }
+
+    #[test]
+    fn test_dnf_repo_file() {
+        let repo = Repository {
+            name: "hashicorp".into(),
+            key_url: Some("https://rpm.releases.hashicorp.com/gpg".into()),
+            url: Some("https://rpm.releases.hashicorp.com/fedora/stable".into()),
+            ..Repository::default()
+        };
+        let contents = repo.dnf_repo(&TemplateContext::new()).unwrap();
+        assert!(contents.starts_with("[hashicorp]\n"));
+        assert!(contents.contains("baseurl=https://rpm.releases.hashicorp.com/fedora/stable\n"));
+        assert!(contents.contains("gpgcheck=1\ngpgkey=https://rpm.releases.hashicorp.com/gpg\n"));
+    }
+
+    #[test]
+    fn test_fingerprints() {
+        let colons = "pub:-:4096:1:8D81803C0EBFCD88:1487788586:::-:::scESA::::::23::0:\n\
+                      fpr:::::::::9DC858229FC7DD38854AE2D88D81803C0EBFCD88:\n\
+                      uid:-::::1487788586::hash::Docker Release (CE deb) <docker@docker.com>::::::::::0:\n";
+        let found = parse_fingerprints(colons);
+        assert_eq!(found, vec!["9DC858229FC7DD38854AE2D88D81803C0EBFCD88"]);
+        assert!(same_fingerprint(
+            &found[0],
+            "9dc8 5822 9fc7 dd38 854a  e2d8 8d81 803c 0ebf cd88"
+        ));
+        assert!(!same_fingerprint(&found[0], "0000"));
+    }
+
+    #[test]
+    fn test_parse_os_release() {
+        let release = parse_os_release(
+            "NAME=\"Ubuntu\"\nID=ubuntu\nID_LIKE=debian\n# comment\nVERSION_CODENAME='noble'\n",
+        );
+        assert_eq!(release["NAME"], "Ubuntu");
+        assert_eq!(release["ID_LIKE"], "debian");
+        assert_eq!(release["VERSION_CODENAME"], "noble");
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, time::Duration};
+
+    use tranquility::core::retry::{
+        Retry, RetryPolicy, package_manager_transient, retry, ssh_transient,
+    };
+
+    fn instant(attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            attempts,
+            initial_delay_ms: 0,
+            max_delay_ms: 0,
+            multiplier: 2.0,
+        }
+    }
+
+    #[test]
+    fn test_delay_grows_and_caps() {
+        let policy = RetryPolicy {
+            attempts: 6,
+            initial_delay_ms: 1_000,
+            max_delay_ms: 5_000,
+            multiplier: 2.0,
+        };
+        assert_eq!(policy.delay(1), Duration::from_secs(1));
+        assert_eq!(policy.delay(2), Duration::from_secs(2));
+        assert_eq!(policy.delay(3), Duration::from_secs(4));
+        assert_eq!(policy.delay(4), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_detects_package_manager_locks() {
+        let apt = "E: Could not get lock /var/lib/dpkg/lock-frontend. It is held by process 1234 (unattended-upgr)";
+        assert!(package_manager_transient(Some(100), apt));
+        assert!(package_manager_transient(
+            Some(1),
+            "Error: Another app is currently holding the yum lock"
+        ));
+        assert!(!package_manager_transient(
+            Some(100),
+            "E: Unable to locate package nosuchpkg"
+        ));
+    }
+
+    #[test]
+    fn test_ssh_only_retries_connection_failures() {
+        let refused = "ssh: connect to host 10.0.0.1 port 22: Connection refused";
+        assert!(ssh_transient(Some(255), refused));
+        // The remote command printed it and failed on its own
+        assert!(!ssh_transient(Some(1), refused));
+        assert!(!ssh_transient(Some(255), "Permission denied (publickey)."));
+    }
+
+    #[test]
+    fn test_retry_stops_on_permanent_error() {
+        let calls = Cell::new(0);
+        let result: Result<(), &str> = retry(
+            &instant(5),
+            "op",
+            || {
+                calls.set(calls.get() + 1);
+                Err(if calls.get() < 2 { "busy" } else { "broken" })
+            },
+            |e| *e == "busy",
+        );
+        assert_eq!(result, Err("broken"));
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn test_retry_gives_up_after_attempts() {
+        let calls = Cell::new(0);
+        let result: Result<(), &str> = retry(
+            &instant(3),
+            "op",
+            || {
+                calls.set(calls.get() + 1);
+                Err("busy")
+            },
+            |_| true,
+        );
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_retries_through_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let counter = dir.path().join("count");
+        // Fails with a dpkg lock message twice, then succeeds
+        let script = format!(
+            "n=$(cat {c} 2>/dev/null || echo 0); n=$((n+1)); echo $n > {c}; \
+             if [ $n -lt 3 ]; then echo 'E: Could not get lock /var/lib/dpkg/lock' >&2; exit 100; fi",
+            c = counter.display()
+        );
+        let retry = Retry {
+            policy: instant(5),
+            ..Retry::package_manager()
+        };
+
+        tranquility::core::shell::ShellCommand::from_script(&script, false)
+            .with_retry(retry)
+            .run_verbose(false)
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&counter).unwrap().trim(), "3");
+    }
+}
@@ -16,6 +16,47 @@ mod tests {
         assert!(!result, "Expected invalid YAML to fail");
     }
 
+    #[test]
+    fn test_unknown_template_variable() {
+        let result = validate_file(Path::new(
+            "tests/fixtures/invalid_template_applications.json",
+        ));
+        assert!(!result, "Expected unknown template variable to fail");
+    }
+
+    #[test]
+    fn test_invalid_step_object() {
+        let result = validate_file(Path::new("tests/fixtures/invalid_step_applications.json"));
+        assert!(
+            !result,
+            "Expected step without `run` or with unknown fields to fail"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_package_version() {
+        let result = validate_file(Path::new(
+            "tests/fixtures/invalid_version_applications.json",
+        ));
+        assert!(!result, "Expected pacman package_version to fail");
+    }
+
+    #[test]
+    fn test_mismatched_package_options() {
+        let result = validate_file(Path::new(
+            "tests/fixtures/invalid_options_applications.json",
+        ));
+        assert!(!result, "Expected snap options on a pacman method to fail");
+    }
+
+    #[test]
+    fn test_repository_without_url() {
+        let result = validate_file(Path::new(
+            "tests/fixtures/invalid_repository_applications.json",
+        ));
+        assert!(!result, "Expected apt repository without url to fail");
+    }
+
     // Remove xml support for now
     // #[test]
     // fn test_valid_xml_file() {
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use tranquility::models::vps::{
+        json::{FlexibleValue, VpsEntry},
+        ssh_config::{BLOCK_BEGIN, BLOCK_END, SshHost, merge_block, parse_hosts, render_block},
+    };
+
+    fn web() -> VpsEntry {
+        VpsEntry {
+            id: Some("web-deploy".into()),
+            name: Some("web".into()),
+            host: "203.0.113.10".into(),
+            user: Some("deploy".into()),
+            port: Some(FlexibleValue::from(2222)),
+            private_key: Some("/home/me/.ssh/web".into()),
+            ..VpsEntry::default()
+        }
+    }
+
+    #[test]
+    fn test_render_block() {
+        assert_eq!(
+            render_block(&[web()]),
+            format!(
+                "{BLOCK_BEGIN}\nHost web-deploy\n    HostName 203.0.113.10\n    User deploy\n    \
+                 Port 2222\n    IdentityFile /home/me/.ssh/web\n\n{BLOCK_END}\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_merge_keeps_global_options_and_other_hosts() {
+        let existing =
+            "Include config.d/*\nServerAliveInterval 30\n\nHost github.com\n    User git\n";
+        let merged = merge_block(existing, &render_block(&[web()]));
+
+        let block_at = merged.find(BLOCK_BEGIN).unwrap();
+        assert!(merged.find("ServerAliveInterval").unwrap() < block_at);
+        assert!(merged.find("Host github.com").unwrap() > block_at);
+
+        // Exporting again replaces the block instead of adding another
+        let again = merge_block(&merged, &render_block(&[]));
+        assert_eq!(again.matches(BLOCK_BEGIN).count(), 1);
+        assert!(!again.contains("web-deploy"));
+        assert!(again.contains("Host github.com\n    User git\n"));
+        assert!(again.starts_with("Include config.d/*\nServerAliveInterval 30\n"));
+    }
+
+    #[test]
+    fn test_merge_replaces_unterminated_block_to_the_end() {
+        let existing = format!("ServerAliveInterval 30\n\n{BLOCK_BEGIN}\nHost old\n    User me\n");
+        let merged = merge_block(&existing, &render_block(&[web()]));
+
+        assert_eq!(merged.matches(BLOCK_BEGIN).count(), 1);
+        assert!(!merged.contains("Host old"));
+        assert!(merged.starts_with("ServerAliveInterval 30\n"));
+        assert!(merged.ends_with(&format!("{BLOCK_END}\n")));
+    }
+
+    #[test]
+    fn test_parse_hosts_skips_wildcards_and_match() {
+        let config = "\
+Host *
+    User nobody
+
+Host db db.internal
+    HostName=10.0.0.5
+    User admin
+    Port 2200
+    IdentityFile \"~/.ssh/db key\"
+
+Match host db
+    User other
+
+Host *.example.com !bastion.example.com
+    ForwardAgent yes
+";
+        let hosts = parse_hosts(config);
+        assert_eq!(hosts.len(), 2);
+        assert_eq!(
+            hosts[0],
+            SshHost {
+                alias: "db".into(),
+                host_name: Some("10.0.0.5".into()),
+                user: Some("admin".into()),
+                port: Some(2200),
+                identity_file: Some("~/.ssh/db key".into()),
+            }
+        );
+        assert_eq!(hosts[1].alias, "db.internal");
+    }
+
+    #[test]
+    fn test_exported_block_parses_back() {
+        let hosts = parse_hosts(&render_block(&[web()]));
+        assert_eq!(hosts.len(), 1);
+
+        let vps = hosts[0].clone().into_vps(&[]);
+        assert_eq!(vps.host, "203.0.113.10");
+        assert_eq!(vps.effective_user(), "deploy");
+        assert_eq!(vps.effective_port(), 2222);
+        assert_eq!(vps.private_key.as_deref(), Some("/home/me/.ssh/web"));
+        assert_eq!(vps.id.as_deref(), Some("web-deploy"));
+
+        // A taken alias falls back to a generated id
+        let vps = hosts[0].clone().into_vps(&[web()]);
+        assert_eq!(vps.id.as_deref(), Some("web-deploy-deploy"));
+    }
+}
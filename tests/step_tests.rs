@@ -0,0 +1,103 @@
+#[cfg(all(test, unix))]
+mod tests {
+    use tranquility::{
+        core::template::TemplateContext,
+        models::application::step::{InstallStep, StepDecision, StepSpec},
+    };
+
+    fn guarded(spec: StepSpec) -> InstallStep {
+        InstallStep::Detailed(StepSpec {
+            run: "echo should-not-run".into(),
+            ..spec
+        })
+    }
+
+    #[test]
+    fn test_plain_step_always_runs() {
+        let step = InstallStep::from("echo hello");
+        let decision = step.check_guards(&TemplateContext::new()).unwrap();
+        assert_eq!(decision, StepDecision::Run);
+    }
+
+    #[test]
+    fn test_creates_skips_existing_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = TemplateContext::new().with_var("dir", dir.path().display().to_string());
+
+        let existing = guarded(StepSpec {
+            creates: Some("{{dir}}".into()),
+            ..StepSpec::default()
+        });
+        assert!(matches!(
+            existing.check_guards(&ctx).unwrap(),
+            StepDecision::Skip(_)
+        ));
+
+        let missing = guarded(StepSpec {
+            creates: Some("{{dir}}/missing".into()),
+            ..StepSpec::default()
+        });
+        assert_eq!(missing.check_guards(&ctx).unwrap(), StepDecision::Run);
+    }
+
+    #[test]
+    fn test_only_if_and_unless() {
+        let ctx = TemplateContext::new();
+        let cases = [
+            (Some("true"), None, StepDecision::Run),
+            (
+                Some("false"),
+                None,
+                StepDecision::Skip("only_if `false` failed".into()),
+            ),
+            (None, Some("false"), StepDecision::Run),
+            (
+                None,
+                Some("true"),
+                StepDecision::Skip("unless `true` succeeded".into()),
+            ),
+        ];
+
+        for (only_if, unless, expected) in cases {
+            let step = guarded(StepSpec {
+                only_if: only_if.map(String::from),
+                unless: unless.map(String::from),
+                ..StepSpec::default()
+            });
+            assert_eq!(step.check_guards(&ctx).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_guards_see_step_env() {
+        let step = guarded(StepSpec {
+            env: [("MARKER".to_string(), "yes".to_string())].into(),
+            unless: Some("test \"$MARKER\" = yes".into()),
+            ..StepSpec::default()
+        });
+        assert!(matches!(
+            step.check_guards(&TemplateContext::new()).unwrap(),
+            StepDecision::Skip(_)
+        ));
+    }
+
+    #[test]
+    fn test_plan_guards_does_not_run_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("ran");
+        let step = guarded(StepSpec {
+            only_if: Some(format!("touch {}", marker.display())),
+            unless: Some("true".into()),
+            ..StepSpec::default()
+        });
+
+        assert_eq!(
+            step.plan_guards(&TemplateContext::new()).unwrap(),
+            StepDecision::Check(format!(
+                "only_if `touch {}`, unless `true`",
+                marker.display()
+            ))
+        );
+        assert!(!marker.exists());
+    }
+}
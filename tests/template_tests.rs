@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod tests {
+    use tranquility::core::template::{TemplateContext, TemplateError, referenced_variables};
+
+    fn context() -> TemplateContext {
+        TemplateContext::new()
+            .with_var("arch", "aarch64")
+            .with_var("version", "1.2.3")
+    }
+
+    #[test]
+    fn test_render_expands_variables() {
+        let rendered = context()
+            .render("https://example.com/{{ version }}/tool-{{arch}}.tar.gz")
+            .unwrap();
+        assert_eq!(rendered, "https://example.com/1.2.3/tool-aarch64.tar.gz");
+    }
+
+    #[test]
+    fn test_render_rejects_unknown_variable() {
+        let err = context().render("echo {{distro}}").unwrap_err();
+        assert_eq!(err, TemplateError::UnknownVariable("distro".into()));
+    }
+
+    #[test]
+    fn test_render_leaves_non_identifiers_alone() {
+        let step = "docker inspect -f '{{.State.Status}}' app && echo {{";
+        assert_eq!(context().render(step).unwrap(), step);
+        assert!(referenced_variables(step).is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    use tranquility::models::vps::{
+        json::{FlexibleValue, VpsEntry},
+        transfer::{Endpoint, TransferOptions, TransferTool, transfer_command},
+    };
+
+    fn vps(host: &str, port: u16, key: Option<&str>) -> VpsEntry {
+        VpsEntry {
+            id: Some(format!("{host}-deploy")),
+            name: Some(host.into()),
+            host: host.into(),
+            user: Some("deploy".into()),
+            port: Some(FlexibleValue::from(port)),
+            private_key: key.map(str::to_string),
+            ..VpsEntry::default()
+        }
+    }
+
+    #[test]
+    fn test_rsync_push_uses_key_and_port() {
+        let web = vps("web.example.com", 2222, Some("/home/me/.ssh/id ed25519"));
+        let cmd = transfer_command(
+            TransferTool::Rsync,
+            &Endpoint::Local("./dist/"),
+            &Endpoint::Remote {
+                vps: &web,
+                path: "/srv/app",
+            },
+            TransferOptions {
+                recursive: true,
+                progress: true,
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(cmd.command, "rsync");
+        assert_eq!(
+            cmd.args,
+            [
+                "-az",
+                "--progress",
+                "-e",
+                "ssh -i '/home/me/.ssh/id ed25519' -p 2222",
+                "./dist/",
+                "deploy@web.example.com:/srv/app",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scp_pull_addresses_remote_by_uri() {
+        let web = vps("web.example.com", 22, None);
+        let cmd = transfer_command(
+            TransferTool::Scp,
+            &Endpoint::Remote {
+                vps: &web,
+                path: "/var/log/app.log",
+            },
+            &Endpoint::Local("."),
+            TransferOptions::default(),
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            cmd.args,
+            [
+                "-q",
+                "scp://deploy@web.example.com:22//var/log/app.log",
+                "."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remote_to_remote_goes_through_local_with_scp() {
+        let a = vps("a.example.com", 22, Some("/k/fleet"));
+        let b = vps("2001:db8::1", 2200, Some("/k/fleet"));
+        let from = Endpoint::Remote {
+            vps: &a,
+            path: "data",
+        };
+        let to = Endpoint::Remote {
+            vps: &b,
+            path: "backup",
+        };
+        let options = TransferOptions {
+            recursive: true,
+            progress: false,
+        };
+
+        let cmd = transfer_command(TransferTool::Scp, &from, &to, options, &[]).unwrap();
+        assert_eq!(
+            cmd.args,
+            [
+                "-3",
+                "-r",
+                "-q",
+                "-i",
+                "/k/fleet",
+                "scp://deploy@a.example.com:22/data",
+                "scp://deploy@[2001:db8::1]:2200/backup",
+            ]
+        );
+        assert!(transfer_command(TransferTool::Rsync, &from, &to, options, &[]).is_err());
+    }
+
+    #[test]
+    fn test_remote_to_remote_refuses_differing_ssh_settings() {
+        let a = vps("a.example.com", 22, Some("/k/a"));
+        let b = vps("b.example.com", 22, Some("/k/b"));
+        let err = transfer_command(
+            TransferTool::Scp,
+            &Endpoint::Remote {
+                vps: &a,
+                path: "data",
+            },
+            &Endpoint::Remote {
+                vps: &b,
+                path: "backup",
+            },
+            TransferOptions::default(),
+            &[],
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn test_local_to_local_is_rejected() {
+        assert!(
+            transfer_command(
+                TransferTool::Scp,
+                &Endpoint::Local("a"),
+                &Endpoint::Local("b"),
+                TransferOptions::default(),
+                &[],
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use tranquility::models::vps::{
+        json::{FlexibleValue, VpsConfig, VpsEntry},
+        tunnel::{TunnelRecord, TunnelSpec, tunnel_command},
+    };
+
+    fn db() -> VpsEntry {
+        VpsEntry {
+            id: Some("db-prod".into()),
+            name: Some("db".into()),
+            host: "10.0.1.5".into(),
+            user: Some("ops".into()),
+            port: Some(FlexibleValue::from(2200)),
+            private_key: Some("/keys/db".into()),
+            tunnels: vec![TunnelSpec {
+                name: "postgres".into(),
+                local_forwards: vec!["5433:localhost:5432".into()],
+                ..TunnelSpec::default()
+            }],
+            ..VpsEntry::default()
+        }
+    }
+
+    fn record(vps_id: &str, name: &str, pid: u32) -> TunnelRecord {
+        TunnelRecord {
+            vps_id: vps_id.into(),
+            name: name.into(),
+            pid,
+            forwards: vec!["-L".into(), "5433:localhost:5432".into()],
+            started_at: "2026-01-01T00:00:00+00:00".into(),
+        }
+    }
+
+    #[test]
+    fn test_tunnel_command() {
+        let vps = db();
+        let cmd = tunnel_command(&vps, &[], &vps.tunnels[0]).unwrap();
+        assert_eq!(cmd.command, "ssh");
+        assert_eq!(
+            cmd.args,
+            [
+                "-N",
+                "-o",
+                "ExitOnForwardFailure=yes",
+                "-o",
+                "BatchMode=yes",
+                "-i",
+                "/keys/db",
+                "-p",
+                "2200",
+                "-L",
+                "5433:localhost:5432",
+                "ops@10.0.1.5",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_entry_forwards_make_default_tunnel() {
+        let vps = VpsEntry {
+            dynamic_forward: Some("1080".into()),
+            ..db()
+        };
+        let spec = TunnelSpec::from_entry(&vps);
+        assert_eq!(spec.name, "default");
+        assert_eq!(spec.forward_args(), ["-D", "1080"]);
+        assert!(TunnelSpec::from_entry(&db()).is_empty());
+    }
+
+    #[test]
+    fn test_records_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        record("web", "socks", 11).save(dir.path()).unwrap();
+        record("db-prod", "postgres", 22).save(dir.path()).unwrap();
+
+        let all = TunnelRecord::load_all(dir.path()).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0], record("db-prod", "postgres", 22));
+        assert_eq!(
+            TunnelRecord::load(dir.path(), "web", "socks"),
+            Some(record("web", "socks", 11))
+        );
+
+        all[0].remove(dir.path()).unwrap();
+        assert_eq!(TunnelRecord::load_all(dir.path()).unwrap().len(), 1);
+        assert!(
+            TunnelRecord::load_all(&dir.path().join("missing"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_dead_tunnel_is_detected() {
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let pid = child.id();
+        child.wait().unwrap();
+        assert!(!record("db-prod", "postgres", pid).is_alive());
+
+        // A live process that isn't ssh doesn't count either
+        let mut sleeper = std::process::Command::new("sleep")
+            .arg("5")
+            .spawn()
+            .unwrap();
+        assert!(!record("db-prod", "postgres", sleeper.id()).is_alive());
+        assert!(tranquility::models::vps::tunnel::process_running(
+            sleeper.id(),
+            "sleep"
+        ));
+        sleeper.kill().unwrap();
+        sleeper.wait().unwrap();
+    }
+
+    #[test]
+    fn test_tunnels_survive_xml() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vps.xml");
+        VpsConfig { vps: vec![db()] }.save_to_file(&path).unwrap();
+
+        let loaded = VpsConfig::load_from_file(&path).unwrap();
+        assert_eq!(loaded.vps[0].tunnels, db().tunnels);
+    }
+}
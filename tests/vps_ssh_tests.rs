@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, time::Duration};
+
+    use tranquility::models::vps::{
+        json::{FlexibleValue, VpsConfig, VpsEntry},
+        ssh::{batch_args, forward_directive, ssh_args},
+        tunnel::TunnelSpec,
+    };
+
+    fn entry(id: &str, host: &str) -> VpsEntry {
+        VpsEntry {
+            id: Some(id.into()),
+            name: Some(id.into()),
+            host: host.into(),
+            user: Some("ops".into()),
+            ..VpsEntry::default()
+        }
+    }
+
+    fn inventory() -> Vec<VpsEntry> {
+        let mut edge = entry("edge", "edge.example.com");
+        edge.port = Some(FlexibleValue::from(2222));
+        edge.private_key = Some("/keys/edge".into());
+
+        let mut bastion = entry("bastion", "10.0.0.1");
+        bastion.proxy_jump = Some("edge".into());
+
+        let mut db = entry("db", "10.0.1.5");
+        db.proxy_jump = Some("bastion".into());
+        db.forward_agent = Some(true);
+        db.local_forwards = vec!["5432:localhost:5432".into()];
+        db.dynamic_forward = Some("1080".into());
+        db.ssh_options = BTreeMap::from([("ServerAliveInterval".into(), "30".into())]);
+
+        vec![edge, bastion, db]
+    }
+
+    #[test]
+    fn test_batch_args() {
+        assert_eq!(
+            batch_args(Duration::from_secs(7)),
+            ["-o", "BatchMode=yes", "-o", "ConnectTimeout=7"]
+        );
+        assert_eq!(batch_args(Duration::ZERO)[3], "ConnectTimeout=1");
+    }
+
+    #[test]
+    fn test_jump_hosts_nest_proxy_commands() {
+        let all = inventory();
+        let args = ssh_args(&all[2], &all).unwrap();
+
+        assert_eq!(
+            args,
+            [
+                "-o",
+                "ProxyCommand=ssh -o 'ProxyCommand=ssh -i /keys/edge -p 2222 ops@edge.example.com -W %%h:%%p' \
+                 ops@10.0.0.1 -W %h:%p",
+                "-A",
+                "-o",
+                "ServerAliveInterval=30",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_jump_loop_is_an_error() {
+        let mut a = entry("a", "a.example.com");
+        a.proxy_jump = Some("b".into());
+        let mut b = entry("b", "b.example.com");
+        b.proxy_jump = Some("a".into());
+        let all = vec![a, b];
+
+        let err = ssh_args(&all[0], &all).unwrap_err();
+        assert!(err.to_string().contains("loop"));
+
+        let config = VpsConfig {
+            vps: vec![VpsEntry {
+                proxy_jump: Some("missing".into()),
+                ..entry("c", "c.example.com")
+            }],
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_forwards() {
+        let all = inventory();
+        assert_eq!(
+            TunnelSpec::from_entry(&all[2]).forward_args(),
+            ["-L", "5432:localhost:5432", "-D", "1080"]
+        );
+        assert_eq!(
+            forward_directive("5432:localhost:5432"),
+            "5432 localhost:5432"
+        );
+        assert_eq!(
+            forward_directive("[::1]:8080:[2001:db8::2]:80"),
+            "[::1]:8080 [2001:db8::2]:80"
+        );
+    }
+
+    #[test]
+    fn test_xml_round_trip_keeps_new_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vps.xml");
+
+        let config = VpsConfig { vps: inventory() };
+        config.save_to_file(&path).unwrap();
+        let loaded = VpsConfig::load_from_file(&path).unwrap();
+
+        let db = &loaded.vps[2];
+        assert_eq!(db.proxy_jump.as_deref(), Some("bastion"));
+        assert_eq!(db.local_forwards, ["5432:localhost:5432"]);
+        assert!(db.remote_forwards.is_empty());
+        assert_eq!(db.dynamic_forward.as_deref(), Some("1080"));
+        assert_eq!(db.forward_agent, Some(true));
+        assert_eq!(db.ssh_options["ServerAliveInterval"], "30");
+        assert_eq!(loaded.vps[0].effective_port(), 2222);
+        assert_eq!(loaded.vps[0].proxy_jump, None);
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, net::TcpListener, thread, time::Duration};
+
+    use tranquility::models::vps::{
+        json::{FlexibleValue, VpsEntry},
+        status::{probe, probe_tcp},
+    };
+
+    const TIMEOUT: Duration = Duration::from_millis(500);
+
+    /// A listener on a free local port that greets each connection with
+    /// `banner`.
+    fn server(banner: &'static str) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let _ = stream.write_all(banner.as_bytes());
+                // Keep the connection open like a server waiting for the client
+                thread::sleep(Duration::from_secs(1));
+            }
+        });
+        port
+    }
+
+    fn closed_port() -> u16 {
+        TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port()
+    }
+
+    fn local(port: u16) -> VpsEntry {
+        VpsEntry {
+            id: Some("local".into()),
+            name: Some("local".into()),
+            host: "127.0.0.1".into(),
+            port: Some(FlexibleValue::from(port)),
+            ..VpsEntry::default()
+        }
+    }
+
+    #[test]
+    fn test_reads_ssh_banner() {
+        let port = server("SSH-2.0-OpenSSH_9.6\r\n");
+        let (_, banner) = probe_tcp("127.0.0.1", port, TIMEOUT).unwrap();
+        assert_eq!(banner.as_deref(), Some("SSH-2.0-OpenSSH_9.6"));
+    }
+
+    #[test]
+    fn test_silent_server_has_no_banner() {
+        let port = server("");
+        let (_, banner) = probe_tcp("127.0.0.1", port, Duration::from_millis(200)).unwrap();
+        assert_eq!(banner, None);
+    }
+
+    #[test]
+    fn test_probe_reports_reachable_host() {
+        let port = server("SSH-2.0-test\n");
+        let status = probe(&local(port), &[], TIMEOUT, false);
+        assert_eq!(status.reachable, Some(true));
+        assert!(status.latency_ms.is_some());
+        assert_eq!(status.banner.as_deref(), Some("SSH-2.0-test"));
+        assert_eq!(status.auth, None);
+        assert!(status.healthy());
+    }
+
+    #[test]
+    fn test_probe_reports_closed_port() {
+        let status = probe(&local(closed_port()), &[], TIMEOUT, false);
+        assert_eq!(status.reachable, Some(false));
+        assert!(status.error.is_some());
+        assert!(!status.healthy());
+    }
+
+    #[test]
+    fn test_jump_host_entries_skip_tcp_probe() {
+        let vps = VpsEntry {
+            proxy_jump: Some("bastion".into()),
+            ..local(closed_port())
+        };
+        let status = probe(&vps, &[], TIMEOUT, false);
+        assert_eq!(status.reachable, None);
+        assert!(status.healthy());
+    }
+}